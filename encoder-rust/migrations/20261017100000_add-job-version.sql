@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN version BIGINT NOT NULL DEFAULT 0;
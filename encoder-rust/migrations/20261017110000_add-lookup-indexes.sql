@@ -0,0 +1,5 @@
+CREATE INDEX IF NOT EXISTS idx_videos_resource_id_created_at ON videos (resource_id, created_at DESC);
+
+-- O índice composto também atende buscas apenas por video_id
+DROP INDEX IF EXISTS idx_jobs_video_id;
+CREATE INDEX IF NOT EXISTS idx_jobs_video_id_created_at ON jobs (video_id, created_at DESC);
@@ -0,0 +1,4 @@
+ALTER TABLE jobs ADD COLUMN idempotency_key TEXT;
+
+-- Só um job não falho por chave: jobs falhos liberam a chave para uma nova submissão
+CREATE UNIQUE INDEX IF NOT EXISTS idx_jobs_idempotency_key ON jobs (idempotency_key) WHERE status <> 'failed';
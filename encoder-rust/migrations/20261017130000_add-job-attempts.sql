@@ -0,0 +1,10 @@
+ALTER TABLE jobs ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE jobs ADD COLUMN max_attempts INTEGER NOT NULL DEFAULT 3;
+ALTER TABLE jobs ADD COLUMN next_attempt_at TIMESTAMPTZ;
+-- Erros de cada tentativa, serializados como um array JSON
+ALTER TABLE jobs ADD COLUMN attempt_errors TEXT NOT NULL DEFAULT '[]';
+
+-- Jobs que já saíram de pending tiveram ao menos uma tentativa
+UPDATE jobs SET attempts = 1 WHERE status <> 'pending';
+
+CREATE INDEX IF NOT EXISTS idx_jobs_status_next_attempt_at ON jobs (status, next_attempt_at);
@@ -0,0 +1,16 @@
+-- Histórico de mudanças de status dos jobs; o id é um UUIDv7, então a ordem
+-- do id é a ordem em que os eventos foram gerados
+CREATE TABLE IF NOT EXISTS job_events (
+    id UUID PRIMARY KEY,
+    job_id UUID NOT NULL,
+    from_status TEXT,
+    to_status TEXT NOT NULL,
+    worker_id TEXT,
+    message TEXT,
+    stage_duration_ms BIGINT,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    CONSTRAINT fk_job_events_job
+        FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_events_job_id ON job_events (job_id, id);
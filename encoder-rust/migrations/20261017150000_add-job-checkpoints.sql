@@ -0,0 +1,3 @@
+-- Estágios concluídos pelo job (download, fragment, encode e objetos já
+-- enviados), serializados como um objeto JSON
+ALTER TABLE jobs ADD COLUMN checkpoints TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,3 @@
+-- Mensagem da fila que criou o job, republicada sem alterações na
+-- dead-letter exchange quando o job falha de vez depois de uma nova tentativa
+ALTER TABLE jobs ADD COLUMN source_message TEXT;
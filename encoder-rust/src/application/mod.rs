@@ -1,23 +1,70 @@
 mod repositories {
+    // Repositórios em memória usados apenas pelos testes dos serviços
+    #[cfg(test)]
+    mod in_memory;
+    mod job_claimer;
+    mod job_creator;
+    mod job_event_repository;
     mod job_repository;
+    mod job_timeline;
+    mod pagination;
     mod repository_error;
     mod repository_trait;
+    mod unit_of_work;
     mod video_repository;
 
-    pub use job_repository::JobRepository;
-    pub use repository_error::{JobRepositoryError, VideoRepositoryError};
+    #[cfg(test)]
+    pub use in_memory::{
+        InMemoryDatabase, InMemoryJobEventRepository, InMemoryJobRepository,
+        InMemoryVideoRepository,
+    };
+    pub use job_claimer::JobClaimer;
+    pub use job_creator::{JobCreator, Submission};
+    pub use job_event_repository::{JobEventQueries, JobEventRepository};
+    pub use job_repository::{JobFilter, JobQueries, JobRepository};
+    pub use job_timeline::JobTimeline;
+    pub use pagination::{Page, Pagination};
+    pub use repository_error::{JobRepositoryError, RepositoryError, VideoRepositoryError};
     pub use repository_trait::Repository;
-    pub use video_repository::VideoRepository;
+    pub use video_repository::{VideoFilter, VideoQueries, VideoRepository};
 }
 
 mod services {
+    mod encoding_error;
+    mod job_consumer;
+    mod job_notifier;
+    mod job_recovery;
+    mod job_worker;
+    mod retry_scheduler;
+    mod upload_manager;
     mod video_service;
+    mod workspace;
 
-    pub use video_service::VideoService;
+    pub use encoding_error::EncodingError;
+    pub use job_consumer::{EncodeRequest, JobConsumer};
+    pub use job_notifier::{JobErrorNotification, JobNotifier};
+    pub use job_recovery::{JobRecovery, RecoveryReport};
+    pub use job_worker::{JobWorker, LeaseLost, StageFailure, unique_worker_id};
+    pub use retry_scheduler::{RetryPolicy, RetryScheduler};
+    pub use upload_manager::{UploadFailure, UploadManager, UploadReport};
+    pub use video_service::{DownloadProgress, VideoService};
+    pub use workspace::{Workspace, WorkspaceError, WorkspaceManager};
 }
 
+#[cfg(test)]
 pub use repositories::{
-    JobRepository, JobRepositoryError, Repository, VideoRepository, VideoRepositoryError,
+    InMemoryDatabase, InMemoryJobEventRepository, InMemoryJobRepository, InMemoryVideoRepository,
 };
 
-pub use services::VideoService;
+pub use repositories::{
+    JobClaimer, JobCreator, JobEventQueries, JobEventRepository, JobFilter, JobQueries,
+    JobRepository, JobRepositoryError, JobTimeline, Page, Pagination, Repository, RepositoryError,
+    Submission, VideoFilter, VideoQueries, VideoRepository, VideoRepositoryError,
+};
+
+pub use services::{
+    DownloadProgress, EncodeRequest, EncodingError, JobConsumer, JobErrorNotification, JobNotifier,
+    JobRecovery, JobWorker, LeaseLost, RecoveryReport, RetryPolicy, RetryScheduler, StageFailure,
+    UploadFailure, UploadManager, UploadReport, VideoService, Workspace, WorkspaceError,
+    WorkspaceManager, unique_worker_id,
+};
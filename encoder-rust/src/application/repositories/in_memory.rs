@@ -0,0 +1,718 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        JobClaimer, JobCreator, JobFilter, JobRepositoryError, JobTimeline, Page, Pagination,
+        Repository, RepositoryError, Submission, VideoFilter, VideoRepositoryError,
+    },
+    domain::{Job, JobEvent, JobStatus, Video},
+};
+
+#[derive(Default)]
+struct State {
+    // Os vídeos são guardados sem a lista de jobs, como na tabela videos
+    videos: HashMap<Uuid, Video>,
+    jobs: HashMap<Uuid, Job>,
+    // Na ordem de gravação, como o ORDER BY id dos UUIDv7 da tabela job_events
+    events: Vec<JobEvent>,
+}
+
+impl State {
+    /// Vídeo como o SQL o carrega ao buscar um job: sem a lista de jobs
+    fn video(&self, id: &Uuid) -> Result<Arc<Video>, RepositoryError> {
+        self.videos
+            .get(id)
+            .cloned()
+            .map(Arc::new)
+            .ok_or_else(|| RepositoryError::not_found("video", id))
+    }
+
+    fn load_job(&self, job: &Job) -> Result<Job, RepositoryError> {
+        Ok(Job {
+            video: self.video(&job.video_id)?,
+            ..job.clone()
+        })
+    }
+
+    fn insert_video(&mut self, item: &Video) -> Result<Video, RepositoryError> {
+        item.validate()?;
+
+        if self.videos.contains_key(&item.id) {
+            return Err(RepositoryError::Conflict(format!(
+                "video {} already exists",
+                item.id
+            )));
+        }
+
+        self.videos.insert(
+            item.id,
+            Video {
+                jobs: Vec::new(),
+                ..item.clone()
+            },
+        );
+
+        Ok(item.clone())
+    }
+
+    fn insert_job(&mut self, item: &Job) -> Result<Job, RepositoryError> {
+        item.validate()?;
+
+        if self.jobs.contains_key(&item.id) {
+            return Err(RepositoryError::Conflict(format!(
+                "job {} already exists",
+                item.id
+            )));
+        }
+        if let Some(key) = &item.idempotency_key
+            && self.find_active_by_key(key).is_some()
+        {
+            return Err(RepositoryError::Conflict(format!(
+                "a job with idempotency key {} already exists",
+                key
+            )));
+        }
+        if !self.videos.contains_key(&item.video_id) {
+            return Err(RepositoryError::ForeignKeyViolation(format!(
+                "video {} does not exist",
+                item.video_id
+            )));
+        }
+
+        let job = self.record_events(item);
+        self.jobs.insert(item.id, job.clone());
+
+        Ok(job)
+    }
+
+    /// Grava os eventos pendentes do job, ignorando os já gravados como o
+    /// ON CONFLICT DO NOTHING, e retorna o job sem eles
+    fn record_events(&mut self, job: &Job) -> Job {
+        for event in &job.events {
+            if !self.events.iter().any(|recorded| recorded.id == event.id) {
+                self.events.push(event.clone());
+            }
+        }
+
+        Job {
+            events: Vec::new(),
+            ..job.clone()
+        }
+    }
+
+    fn create_with_video(&mut self, job: &Job) -> Result<Job, RepositoryError> {
+        self.insert_video(&job.video)?;
+
+        // Desfaz a inserção do vídeo, como o rollback da transação
+        self.insert_job(job).inspect_err(|_| {
+            self.videos.remove(&job.video_id);
+        })
+    }
+
+    /// Mesma condição do índice único parcial idx_jobs_idempotency_key
+    fn find_active_by_key(&self, key: &str) -> Option<&Job> {
+        self.jobs.values().find(|job| {
+            job.idempotency_key.as_deref() == Some(key) && job.status != JobStatus::Failed
+        })
+    }
+}
+
+/// Banco em memória com as mesmas regras dos repositórios SQL (not found,
+/// unicidade, chave estrangeira, cascade e versão), para testes sem sqlx.
+/// Clones compartilham os mesmos dados
+#[derive(Clone, Default)]
+pub struct InMemoryDatabase {
+    state: Arc<Mutex<State>>,
+}
+
+impl InMemoryDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn videos(&self) -> InMemoryVideoRepository {
+        InMemoryVideoRepository { db: self.clone() }
+    }
+
+    pub fn jobs(&self) -> InMemoryJobRepository {
+        InMemoryJobRepository { db: self.clone() }
+    }
+
+    pub fn events(&self) -> InMemoryJobEventRepository {
+        InMemoryJobEventRepository { db: self.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct InMemoryVideoRepository {
+    pub db: InMemoryDatabase,
+}
+
+#[derive(Clone)]
+pub struct InMemoryJobRepository {
+    pub db: InMemoryDatabase,
+}
+
+#[derive(Clone)]
+pub struct InMemoryJobEventRepository {
+    pub db: InMemoryDatabase,
+}
+
+impl InMemoryVideoRepository {
+    /// Mesmo comportamento de `VideoRepository::find_by_resource_id`
+    pub async fn find_by_resource_id(
+        &self,
+        resource_id: &str,
+    ) -> Result<Vec<Video>, VideoRepositoryError> {
+        let mut keys: Vec<_> = self
+            .db
+            .state
+            .lock()
+            .unwrap()
+            .videos
+            .values()
+            .filter(|video| video.resource_id == resource_id)
+            .map(|video| std::cmp::Reverse((video.created_at, video.id)))
+            .collect();
+        keys.sort();
+
+        let mut videos = Vec::with_capacity(keys.len());
+        for std::cmp::Reverse((_, id)) in keys {
+            videos.push(self.find(&id).await?);
+        }
+
+        Ok(videos)
+    }
+}
+
+impl Repository<Video> for InMemoryVideoRepository {
+    type Error = VideoRepositoryError;
+    type Filter = VideoFilter;
+
+    async fn insert(&self, item: &Video) -> Result<Video, Self::Error> {
+        self.db.state.lock().unwrap().insert_video(item)
+    }
+
+    /// Busca o vídeo com os seus jobs, do mais recente para o mais antigo,
+    /// como o LEFT JOIN do VideoRepository
+    async fn find(&self, id: &Uuid) -> Result<Video, Self::Error> {
+        let state = self.db.state.lock().unwrap();
+        let video = state.video(id)?;
+
+        let mut jobs: Vec<_> = state
+            .jobs
+            .values()
+            .filter(|job| job.video_id == *id)
+            .map(|job| {
+                Arc::new(Job {
+                    video: Arc::clone(&video),
+                    ..job.clone()
+                })
+            })
+            .collect();
+        jobs.sort_by_key(|job| std::cmp::Reverse((job.created_at, job.id)));
+
+        Ok(Video {
+            jobs,
+            ..video.as_ref().clone()
+        })
+    }
+
+    async fn update(&self, item: &Video) -> Result<Video, Self::Error> {
+        item.validate()?;
+
+        let mut state = self.db.state.lock().unwrap();
+        let video = state
+            .videos
+            .get_mut(&item.id)
+            .ok_or_else(|| VideoRepositoryError::not_found("video", item.id))?;
+        *video = Video {
+            jobs: Vec::new(),
+            ..item.clone()
+        };
+
+        Ok(item.clone())
+    }
+
+    async fn list(
+        &self,
+        filter: &VideoFilter,
+        pagination: Pagination,
+    ) -> Result<Page<Video>, Self::Error> {
+        let state = self.db.state.lock().unwrap();
+
+        let mut videos: Vec<_> = state
+            .videos
+            .values()
+            .filter(|video| {
+                filter
+                    .resource_id
+                    .as_ref()
+                    .is_none_or(|resource_id| video.resource_id == *resource_id)
+            })
+            .cloned()
+            .collect();
+        videos.sort_by_key(|video| std::cmp::Reverse((video.created_at, video.id)));
+
+        Ok(Page::from_overfetch(
+            paginate(videos, pagination),
+            pagination,
+        ))
+    }
+
+    /// Remove o vídeo e, como o ON DELETE CASCADE, os seus jobs e eventos
+    async fn delete(&self, id: &Uuid) -> Result<(), Self::Error> {
+        let mut state = self.db.state.lock().unwrap();
+        let state = &mut *state;
+
+        state
+            .videos
+            .remove(id)
+            .ok_or_else(|| VideoRepositoryError::not_found("video", id))?;
+        state.jobs.retain(|_, job| job.video_id != *id);
+
+        let jobs = &state.jobs;
+        state
+            .events
+            .retain(|event| jobs.contains_key(&event.job_id));
+
+        Ok(())
+    }
+}
+
+impl InMemoryJobRepository {
+    /// Mesmo comportamento de `JobRepository::find_by_video_id`
+    pub async fn find_by_video_id(&self, video_id: &Uuid) -> Result<Vec<Job>, JobRepositoryError> {
+        let state = self.db.state.lock().unwrap();
+
+        let mut jobs: Vec<_> = state
+            .jobs
+            .values()
+            .filter(|job| job.video_id == *video_id)
+            .collect();
+        jobs.sort_by_key(|job| std::cmp::Reverse((job.created_at, job.id)));
+
+        jobs.into_iter().map(|job| state.load_job(job)).collect()
+    }
+}
+
+impl Repository<Job> for InMemoryJobRepository {
+    type Error = JobRepositoryError;
+    type Filter = JobFilter;
+
+    async fn insert(&self, item: &Job) -> Result<Job, Self::Error> {
+        self.db.state.lock().unwrap().insert_job(item)
+    }
+
+    async fn find(&self, id: &Uuid) -> Result<Job, Self::Error> {
+        let state = self.db.state.lock().unwrap();
+        let job = state
+            .jobs
+            .get(id)
+            .ok_or_else(|| JobRepositoryError::not_found("job", id))?;
+
+        state.load_job(job)
+    }
+
+    async fn list(
+        &self,
+        filter: &JobFilter,
+        pagination: Pagination,
+    ) -> Result<Page<Job>, Self::Error> {
+        let state = self.db.state.lock().unwrap();
+
+        let mut jobs: Vec<_> = state
+            .jobs
+            .values()
+            .filter(|job| matches_filter(job, filter))
+            .collect();
+        jobs.sort_by_key(|job| std::cmp::Reverse((job.created_at, job.id)));
+
+        let jobs = paginate(jobs, pagination)
+            .into_iter()
+            .map(|job| state.load_job(job))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Page::from_overfetch(jobs, pagination))
+    }
+
+    /// Remove o job e os seus eventos, retornando NotFound se ele não existir
+    async fn delete(&self, id: &Uuid) -> Result<(), Self::Error> {
+        let mut state = self.db.state.lock().unwrap();
+
+        state
+            .jobs
+            .remove(id)
+            .ok_or_else(|| JobRepositoryError::not_found("job", id))?;
+        state.events.retain(|event| event.job_id != *id);
+
+        Ok(())
+    }
+
+    /// Atualiza os mesmos campos do UPDATE do JobRepository, com a mesma checagem de versão
+    async fn update(&self, item: &Job) -> Result<Job, Self::Error> {
+        let mut state = self.db.state.lock().unwrap();
+        let stored = state
+            .jobs
+            .get_mut(&item.id)
+            .ok_or_else(|| JobRepositoryError::not_found("job", item.id))?;
+
+        if stored.version != item.version {
+            return Err(JobRepositoryError::VersionConflict {
+                entity: "job",
+                key: item.id.to_string(),
+                version: item.version,
+            });
+        }
+
+        stored.output_bucket_path = item.output_bucket_path.clone();
+        stored.status = item.status;
+        stored.error = item.error.clone();
+        stored.worker_id = item.worker_id.clone();
+        stored.lease_expires_at = item.lease_expires_at;
+        stored.attempts = item.attempts;
+        stored.next_attempt_at = item.next_attempt_at;
+        stored.attempt_errors = item.attempt_errors.clone();
+        stored.checkpoints = item.checkpoints.clone();
+        stored.updated_at = item.updated_at;
+        stored.version += 1;
+
+        let version = stored.version;
+        Ok(Job {
+            version,
+            ..state.record_events(item)
+        })
+    }
+}
+
+impl JobClaimer for InMemoryJobRepository {
+    async fn claim_next(
+        &self,
+        worker_id: &str,
+        lease: chrono::Duration,
+    ) -> Result<Option<Job>, JobRepositoryError> {
+        let mut state = self.db.state.lock().unwrap();
+
+        let next = state
+            .jobs
+            .values_mut()
+            .filter(|job| job.status == JobStatus::Pending)
+            .min_by_key(|job| (job.created_at, job.id));
+
+        let Some(job) = next else {
+            return Ok(None);
+        };
+
+        job.claim(worker_id, lease)?;
+        job.version += 1;
+
+        let job = job.clone();
+        let job = state.record_events(&job);
+        state.jobs.insert(job.id, job.clone());
+
+        state.load_job(&job).map(Some)
+    }
+
+    async fn renew_lease(
+        &self,
+        job_id: &Uuid,
+        worker_id: &str,
+        lease_expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), JobRepositoryError> {
+        let mut state = self.db.state.lock().unwrap();
+
+        match state.jobs.get_mut(job_id) {
+            Some(job) if job.worker_id.as_deref() == Some(worker_id) => {
+                job.lease_expires_at = Some(lease_expires_at);
+                Ok(())
+            }
+            _ => Err(JobRepositoryError::not_found("job", job_id)),
+        }
+    }
+}
+
+impl JobCreator for InMemoryJobRepository {
+    async fn create_with_video(&self, job: &Job) -> Result<Job, JobRepositoryError> {
+        self.db.state.lock().unwrap().create_with_video(job)
+    }
+
+    async fn submit(&self, job: &Job, force: bool) -> Result<Submission, JobRepositoryError> {
+        let mut state = self.db.state.lock().unwrap();
+
+        let active = job
+            .idempotency_key
+            .as_deref()
+            .and_then(|key| state.find_active_by_key(key))
+            .map(|existing| existing.id);
+
+        let Some(active) = active else {
+            return state.create_with_video(job).map(Submission::Created);
+        };
+
+        if !force {
+            let existing = state.jobs[&active].clone();
+            return state.load_job(&existing).map(Submission::Existing);
+        }
+
+        // A chave passa para o novo job e volta ao anterior se a criação falhar
+        let key = state
+            .jobs
+            .get_mut(&active)
+            .and_then(|existing| existing.idempotency_key.take());
+
+        state
+            .create_with_video(job)
+            .map(Submission::Created)
+            .inspect_err(|_| {
+                if let Some(existing) = state.jobs.get_mut(&active) {
+                    existing.idempotency_key = key;
+                }
+            })
+    }
+}
+
+impl JobTimeline for InMemoryJobEventRepository {
+    async fn timeline(&self, job_id: &Uuid) -> Result<Vec<JobEvent>, JobRepositoryError> {
+        let state = self.db.state.lock().unwrap();
+
+        let events: Vec<_> = state
+            .events
+            .iter()
+            .filter(|event| event.job_id == *job_id)
+            .cloned()
+            .collect();
+
+        if events.is_empty() && !state.jobs.contains_key(job_id) {
+            return Err(JobRepositoryError::not_found("job", job_id));
+        }
+
+        Ok(events)
+    }
+}
+
+fn matches_filter(job: &Job, filter: &JobFilter) -> bool {
+    filter.status.is_none_or(|status| job.status == status)
+        && filter.video_id.is_none_or(|id| job.video_id == id)
+        && filter.created_after.is_none_or(|t| job.created_at >= t)
+        && filter.created_before.is_none_or(|t| job.created_at < t)
+        && filter.updated_after.is_none_or(|t| job.updated_at >= t)
+        && filter.updated_before.is_none_or(|t| job.updated_at < t)
+        && filter
+            .next_attempt_before
+            .is_none_or(|t| job.next_attempt_at.is_some_and(|at| at < t))
+        && filter
+            .lease_expired_before
+            .is_none_or(|t| job.lease_expires_at.is_some_and(|at| at < t))
+        && (!filter.without_lease || job.lease_expires_at.is_none())
+}
+
+/// Aplica o offset e mantém uma linha além do limite, como o LIMIT + 1 do SQL
+fn paginate<T>(items: Vec<T>, pagination: Pagination) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(pagination.offset as usize)
+        .take(pagination.limit as usize + 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_video() -> Video {
+        Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_repositories_find_video_with_jobs_and_cascade() {
+        let db = InMemoryDatabase::new();
+        let (videos, jobs) = (db.videos(), db.jobs());
+
+        let video = new_video();
+        videos.insert(&video).await.expect("Failed to insert video");
+
+        let job1 = Job::new("/output/path1".to_string(), Arc::new(video.clone()));
+        let mut job2 = Job::new("/output/path2".to_string(), Arc::new(video.clone()));
+        job2.fail("encode failed").expect("Failed to fail job");
+        jobs.insert(&job1).await.expect("Failed to insert job");
+        jobs.insert(&job2).await.expect("Failed to insert job");
+
+        let found = videos.find(&video.id).await.expect("Failed to find video");
+        assert_eq!(found.jobs.len(), 2);
+        assert!(found.jobs.iter().all(|job| job.video.id == video.id));
+        assert!(found.jobs.iter().any(|job| job.status == JobStatus::Failed));
+
+        let by_video = jobs
+            .find_by_video_id(&video.id)
+            .await
+            .expect("Failed to find jobs");
+        assert_eq!(by_video.len(), 2);
+        assert!(by_video[0].created_at >= by_video[1].created_at);
+
+        let by_resource = videos
+            .find_by_resource_id("resource_123")
+            .await
+            .expect("Failed to find video");
+        assert_eq!(by_resource.len(), 1);
+        assert_eq!(by_resource[0].id, video.id);
+        assert_eq!(by_resource[0].jobs.len(), 2);
+
+        let found_job = jobs.find(&job1.id).await.expect("Failed to find job");
+        assert_eq!(found_job.video.resource_id, "resource_123");
+        assert!(found_job.video.jobs.is_empty());
+
+        videos
+            .delete(&video.id)
+            .await
+            .expect("Failed to delete video");
+
+        assert!(
+            videos
+                .find(&video.id)
+                .await
+                .is_err_and(|e| e.is_not_found())
+        );
+        assert!(jobs.find(&job1.id).await.is_err_and(|e| e.is_not_found()));
+        assert!(jobs.find(&job2.id).await.is_err_and(|e| e.is_not_found()));
+        assert!(
+            videos
+                .delete(&video.id)
+                .await
+                .is_err_and(|e| e.is_not_found())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_repositories_enforce_constraints() {
+        let db = InMemoryDatabase::new();
+        let (videos, jobs) = (db.videos(), db.jobs());
+
+        let video = new_video();
+        let job = Job::new("/output/path".to_string(), Arc::new(video.clone()));
+
+        let result = jobs.insert(&job).await;
+        assert!(matches!(
+            result,
+            Err(RepositoryError::ForeignKeyViolation(_))
+        ));
+
+        let invalid = Video::new(String::new(), "/path/to/video.mp4".to_string());
+        let result = videos.insert(&invalid).await;
+        assert!(matches!(result, Err(RepositoryError::Validation(_))));
+
+        videos.insert(&video).await.expect("Failed to insert video");
+        let result = videos.insert(&video).await;
+        assert!(matches!(result, Err(RepositoryError::Conflict(_))));
+
+        // Mesma checagem de versão do UPDATE do JobRepository
+        jobs.insert(&job).await.expect("Failed to insert job");
+        let mut stale = job.clone();
+
+        let mut current = jobs.find(&job.id).await.expect("Failed to find job");
+        current.transition_to(JobStatus::Downloading).unwrap();
+        let current = jobs.update(&current).await.expect("Failed to update job");
+        assert_eq!(current.version, 1);
+
+        stale.fail("late failure").unwrap();
+        let result = jobs.update(&stale).await;
+        assert!(result.is_err_and(|e| e.is_version_conflict()));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_job_repository_claims_and_lists() {
+        let db = InMemoryDatabase::new();
+        let jobs = db.jobs();
+        let lease = chrono::Duration::minutes(5);
+
+        let video = Arc::new(new_video());
+        let mut oldest = Job::new("/output/oldest".to_string(), Arc::clone(&video));
+        oldest.created_at -= chrono::Duration::minutes(1);
+        let newest = Job::new("/output/newest".to_string(), Arc::clone(&video));
+
+        jobs.create_with_video(&oldest)
+            .await
+            .expect("Failed to create job");
+        jobs.insert(&newest).await.expect("Failed to insert job");
+
+        // O vídeo já existe, então a criação é desfeita por inteiro
+        let duplicate = Job::new("/output/duplicate".to_string(), Arc::clone(&video));
+        let result = jobs.create_with_video(&duplicate).await;
+        assert!(matches!(result, Err(RepositoryError::Conflict(_))));
+
+        let claimed = jobs
+            .claim_next("worker-1", lease)
+            .await
+            .expect("Failed to claim job")
+            .expect("A pending job should be claimed");
+        assert_eq!(claimed.id, oldest.id);
+        assert_eq!(claimed.status, JobStatus::Downloading);
+        assert_eq!(claimed.version, 1);
+
+        let filter = JobFilter {
+            status: Some(JobStatus::Pending),
+            ..Default::default()
+        };
+        let page = jobs
+            .list(&filter, Pagination::default())
+            .await
+            .expect("Failed to list jobs");
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].id, newest.id);
+
+        let page = jobs
+            .list(&JobFilter::default(), Pagination::new(1, 0))
+            .await
+            .expect("Failed to list jobs");
+        assert_eq!(page.items[0].id, newest.id);
+        assert_eq!(page.next, Some(Pagination::new(1, 1)));
+
+        jobs.claim_next("worker-2", lease).await.unwrap();
+        let none = jobs.claim_next("worker-3", lease).await.unwrap();
+        assert!(none.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_job_event_repository_records_timeline() {
+        let db = InMemoryDatabase::new();
+        let (jobs, events) = (db.jobs(), db.events());
+
+        let job = Job::new("/output/path".to_string(), Arc::new(new_video()));
+        jobs.create_with_video(&job)
+            .await
+            .expect("Failed to create job");
+
+        let mut claimed = jobs
+            .claim_next("worker-1", chrono::Duration::minutes(5))
+            .await
+            .unwrap()
+            .expect("A pending job should be claimed");
+        assert!(claimed.events.is_empty());
+
+        claimed.fail("download failed").unwrap();
+        jobs.update(&claimed).await.expect("Failed to update job");
+
+        let timeline = events
+            .timeline(&job.id)
+            .await
+            .expect("Failed to load timeline");
+        let statuses: Vec<_> = timeline.iter().map(|e| e.to_status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                JobStatus::Pending,
+                JobStatus::Downloading,
+                JobStatus::Failed
+            ]
+        );
+        assert_eq!(timeline[2].message.as_deref(), Some("download failed"));
+
+        jobs.delete(&job.id).await.expect("Failed to delete job");
+        let result = events.timeline(&job.id).await;
+        assert!(result.is_err_and(|e| e.is_not_found()));
+    }
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{application::JobRepositoryError, domain::Job};
+
+/// Reivindicação atômica de jobs pendentes, para que várias réplicas do
+/// encoder possam consumir a mesma tabela sem processar um job duas vezes
+#[allow(async_fn_in_trait)]
+pub trait JobClaimer: Send + Sync {
+    /// Move o job pendente mais antigo para downloading, registrando o worker
+    /// e a expiração da reivindicação. Retorna `None` se não houver jobs pendentes
+    async fn claim_next(
+        &self,
+        worker_id: &str,
+        lease: chrono::Duration,
+    ) -> Result<Option<Job>, JobRepositoryError>;
+
+    /// Estende a reivindicação até `lease_expires_at` enquanto o job ainda
+    /// pertence ao worker, sem alterar a versão do job. Retorna NotFound se
+    /// o job foi removido ou reivindicado por outro worker
+    async fn renew_lease(
+        &self,
+        job_id: &Uuid,
+        worker_id: &str,
+        lease_expires_at: DateTime<Utc>,
+    ) -> Result<(), JobRepositoryError>;
+}
@@ -0,0 +1,34 @@
+use crate::{application::JobRepositoryError, domain::Job};
+
+/// Resultado de uma submissão idempotente
+#[derive(Debug)]
+pub enum Submission {
+    /// Um novo vídeo e um novo job foram gravados
+    Created(Job),
+    /// Já existia um job não falho com a mesma chave de idempotência
+    Existing(Job),
+}
+
+impl Submission {
+    pub fn is_created(&self) -> bool {
+        matches!(self, Submission::Created(_))
+    }
+
+    pub fn into_job(self) -> Job {
+        match self {
+            Submission::Created(job) | Submission::Existing(job) => job,
+        }
+    }
+}
+
+/// Criação atômica de um vídeo junto com o seu primeiro job, para que uma
+/// falha entre as duas inserções não deixe um vídeo sem job
+#[allow(async_fn_in_trait)]
+pub trait JobCreator: Send + Sync {
+    async fn create_with_video(&self, job: &Job) -> Result<Job, JobRepositoryError>;
+
+    /// Como `create_with_video`, mas retorna o job não falho que já detém a
+    /// `idempotency_key` do job em vez de criar outro. Com `force`, a chave
+    /// passa para o novo job e o anterior segue sem ela
+    async fn submit(&self, job: &Job, force: bool) -> Result<Submission, JobRepositoryError>;
+}
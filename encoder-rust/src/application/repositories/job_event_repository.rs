@@ -0,0 +1,328 @@
+use uuid::Uuid;
+
+use crate::{
+    application::{JobRepositoryError, JobTimeline},
+    domain::{JobEvent, JobStatus},
+    framework::Database,
+};
+
+#[derive(sqlx::FromRow)]
+struct JobEventRow {
+    id: Uuid,
+    job_id: Uuid,
+    from_status: Option<String>,
+    to_status: String,
+    worker_id: Option<String>,
+    message: Option<String>,
+    stage_duration_ms: Option<i64>,
+    occurred_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Queries SQL como constantes
+// Gravar o mesmo evento duas vezes (um job salvo de novo a partir de uma cópia
+// antiga) não duplica o histórico
+const INSERT_JOB_EVENT_QUERY: &str = r#"
+    INSERT INTO job_events (
+        id, job_id, from_status, to_status, worker_id, message, stage_duration_ms, occurred_at
+    )
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+    ON CONFLICT (id) DO NOTHING
+"#;
+
+const FIND_JOB_EVENTS_QUERY: &str = r#"
+    SELECT
+        id, job_id, from_status, to_status, worker_id, message, stage_duration_ms, occurred_at
+    FROM job_events
+    WHERE job_id = $1
+    ORDER BY id
+"#;
+
+const FIND_LATEST_JOB_EVENT_QUERY: &str = r#"
+    SELECT
+        id, job_id, from_status, to_status, worker_id, message, stage_duration_ms, occurred_at
+    FROM job_events
+    WHERE job_id = $1
+    ORDER BY id DESC
+    LIMIT 1
+"#;
+
+const FIND_JOB_ID_QUERY: &str = "SELECT id FROM jobs WHERE id = $1";
+
+/// Queries do repositório sobre uma conexão explícita, para que os eventos
+/// sejam gravados na mesma transação da mudança de status
+#[allow(async_fn_in_trait)]
+pub trait JobEventQueries<DB>
+where
+    DB: sqlx::Database,
+{
+    async fn append_on(
+        conn: &mut DB::Connection,
+        events: &[JobEvent],
+    ) -> Result<(), JobRepositoryError>;
+    async fn latest_on(
+        conn: &mut DB::Connection,
+        job_id: &Uuid,
+    ) -> Result<Option<JobEvent>, JobRepositoryError>;
+    async fn timeline_on(
+        conn: &mut DB::Connection,
+        job_id: &Uuid,
+    ) -> Result<Vec<JobEvent>, JobRepositoryError>;
+}
+
+pub struct JobEventRepository<DB>
+where
+    DB: sqlx::Database,
+{
+    pub db: Database<DB>,
+}
+
+impl<DB> Clone for JobEventRepository<DB>
+where
+    DB: sqlx::Database,
+{
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+        }
+    }
+}
+
+impl<DB> JobEventRepository<DB>
+where
+    DB: sqlx::Database,
+{
+    pub fn new(db: Database<DB>) -> Self {
+        Self { db }
+    }
+
+    fn map_event_from_row(row: JobEventRow) -> Result<JobEvent, JobRepositoryError> {
+        let parse = |status: &str| {
+            status
+                .parse::<JobStatus>()
+                .map_err(|e| JobRepositoryError::Decode(e.to_string()))
+        };
+
+        Ok(JobEvent {
+            id: row.id,
+            job_id: row.job_id,
+            from_status: row.from_status.as_deref().map(parse).transpose()?,
+            to_status: parse(&row.to_status)?,
+            worker_id: row.worker_id,
+            message: row.message,
+            stage_duration_ms: row.stage_duration_ms,
+            occurred_at: row.occurred_at,
+        })
+    }
+}
+
+impl<DB> JobEventQueries<DB> for JobEventRepository<DB>
+where
+    DB: sqlx::Database,
+    // Suporte aos tipos usados nas queries
+    for<'q> Uuid: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    for<'q> String: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    for<'q> Option<String>: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    for<'q> Option<i64>: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    for<'q> chrono::DateTime<chrono::Utc>:
+        sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    // Suporte a referências nas queries
+    for<'q> &'q Uuid: sqlx::Encode<'q, DB>,
+    for<'q> &'q Option<String>: sqlx::Encode<'q, DB>,
+    // Suporte aos argumentos e executor do sqlx
+    for<'q> <DB as sqlx::Database>::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+    for<'c> &'c mut <DB as sqlx::Database>::Connection: sqlx::Executor<'c, Database = DB>,
+    // Suporte a indexação de colunas por posição e por nome
+    usize: sqlx::ColumnIndex<DB::Row>,
+    for<'r> &'r str: sqlx::ColumnIndex<DB::Row>,
+{
+    /// Grava os eventos na ordem recebida
+    async fn append_on(
+        conn: &mut DB::Connection,
+        events: &[JobEvent],
+    ) -> Result<(), JobRepositoryError> {
+        for event in events {
+            sqlx::query(INSERT_JOB_EVENT_QUERY)
+                .bind(event.id)
+                .bind(event.job_id)
+                .bind(event.from_status.map(|s| s.to_string()))
+                .bind(event.to_status.to_string())
+                .bind(&event.worker_id)
+                .bind(&event.message)
+                .bind(event.stage_duration_ms)
+                .bind(event.occurred_at)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Busca o evento mais recente do job, se houver
+    async fn latest_on(
+        conn: &mut DB::Connection,
+        job_id: &Uuid,
+    ) -> Result<Option<JobEvent>, JobRepositoryError> {
+        sqlx::query_as::<_, JobEventRow>(FIND_LATEST_JOB_EVENT_QUERY)
+            .bind(job_id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .map(Self::map_event_from_row)
+            .transpose()
+    }
+
+    /// Busca os eventos do job do mais antigo para o mais recente
+    async fn timeline_on(
+        conn: &mut DB::Connection,
+        job_id: &Uuid,
+    ) -> Result<Vec<JobEvent>, JobRepositoryError> {
+        let rows = sqlx::query_as::<_, JobEventRow>(FIND_JOB_EVENTS_QUERY)
+            .bind(job_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        // Um job sem eventos pode existir (criado antes do histórico) ou não
+        if rows.is_empty() {
+            sqlx::query_as::<_, (Uuid,)>(FIND_JOB_ID_QUERY)
+                .bind(job_id)
+                .fetch_optional(&mut *conn)
+                .await?
+                .ok_or_else(|| JobRepositoryError::not_found("job", job_id))?;
+        }
+
+        rows.into_iter().map(Self::map_event_from_row).collect()
+    }
+}
+
+impl<DB> JobTimeline for JobEventRepository<DB>
+where
+    DB: sqlx::Database,
+    Self: JobEventQueries<DB>,
+{
+    async fn timeline(&self, job_id: &Uuid) -> Result<Vec<JobEvent>, JobRepositoryError> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::timeline_on(&mut conn, job_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::Sqlite;
+    use std::{env, sync::Arc};
+
+    use crate::{
+        application::{
+            JobClaimer, JobEventQueries, JobEventRepository, JobRepository, JobTimeline,
+            Repository, VideoRepository,
+        },
+        domain::{Job, JobStatus, Video},
+        framework::Database,
+    };
+
+    async fn setup_test_db() -> Database<Sqlite> {
+        let database_url =
+            env::var("DATABASE_URL_TEST").unwrap_or_else(|_| "sqlite::memory:".to_string());
+
+        Database::<Sqlite>::new(database_url, Some(true))
+            .await
+            .expect("Failed to create test database connection")
+    }
+
+    #[tokio::test]
+    async fn test_job_event_repository_records_every_status_change() {
+        let db = setup_test_db().await;
+        let job_repo = JobRepository::new(db.clone());
+        let event_repo = JobEventRepository::new(db.clone());
+
+        let video = Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string());
+        VideoRepository::new(db.clone())
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+
+        let job = Job::new("/output/path".to_string(), Arc::new(video));
+        let inserted = job_repo.insert(&job).await.expect("Failed to insert job");
+        assert!(inserted.events.is_empty());
+
+        let mut claimed = job_repo
+            .claim_next("worker-1", chrono::Duration::minutes(5))
+            .await
+            .expect("Failed to claim job")
+            .expect("A pending job should be claimed");
+
+        claimed.transition_to(JobStatus::Fragmenting).unwrap();
+        claimed.fail("mp4fragment crashed").unwrap();
+        job_repo
+            .update(&claimed)
+            .await
+            .expect("Failed to update job");
+
+        // Salvar de novo a cópia antiga é rejeitado pelo controle de versão
+        let result = job_repo.update(&claimed).await;
+        assert!(result.is_err_and(|e| e.is_version_conflict()));
+
+        let timeline = event_repo
+            .timeline(&job.id)
+            .await
+            .expect("Failed to load timeline");
+
+        // Gravar de novo os mesmos eventos não duplica o histórico
+        let mut conn = db
+            .conn
+            .acquire()
+            .await
+            .expect("Failed to acquire connection");
+        JobEventRepository::<Sqlite>::append_on(&mut conn, &timeline)
+            .await
+            .expect("Failed to append events again");
+        drop(conn);
+        assert_eq!(
+            event_repo
+                .timeline(&job.id)
+                .await
+                .expect("Failed to load timeline")
+                .len(),
+            timeline.len()
+        );
+
+        let transitions: Vec<_> = timeline
+            .iter()
+            .map(|e| (e.from_status, e.to_status, e.worker_id.as_deref()))
+            .collect();
+        assert_eq!(
+            transitions,
+            vec![
+                (None, JobStatus::Pending, None),
+                (
+                    Some(JobStatus::Pending),
+                    JobStatus::Downloading,
+                    Some("worker-1")
+                ),
+                (
+                    Some(JobStatus::Downloading),
+                    JobStatus::Fragmenting,
+                    Some("worker-1")
+                ),
+                (
+                    Some(JobStatus::Fragmenting),
+                    JobStatus::Failed,
+                    Some("worker-1")
+                ),
+            ]
+        );
+
+        assert_eq!(timeline[0].stage_duration_ms, None);
+        assert!(timeline[1..].iter().all(|e| e.stage_duration_ms.is_some()));
+        assert_eq!(timeline[3].message.as_deref(), Some("mp4fragment crashed"));
+
+        let result = event_repo.timeline(&uuid::Uuid::new_v4()).await;
+        assert!(result.is_err_and(|e| e.is_not_found()));
+
+        // Os eventos são removidos junto com o job
+        job_repo
+            .delete(&job.id)
+            .await
+            .expect("Failed to delete job");
+        let result = event_repo.timeline(&job.id).await;
+        assert!(result.is_err_and(|e| e.is_not_found()));
+    }
+}
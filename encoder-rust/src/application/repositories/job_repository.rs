@@ -1,33 +1,212 @@
+use sqlx::{Postgres, Sqlite};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    application::{JobRepositoryError, Repository},
-    domain::{Job, Video},
+    application::{
+        JobClaimer, JobCreator, JobEventQueries, JobEventRepository, JobRepositoryError, Page,
+        Pagination, Repository, Submission, VideoQueries, VideoRepository,
+    },
+    domain::{Job, JobEvent, JobStatus, Video},
     framework::Database,
 };
 
-// Type aliases para melhor legibilidade
-type JobRow = (
-    Uuid,
-    String,
-    String,
-    Uuid,
-    Option<String>,
-    chrono::DateTime<chrono::Utc>,
-    chrono::DateTime<chrono::Utc>,
-);
+// Linhas lidas por nome de coluna: jobs passaram do limite de colunas das tuplas do sqlx
+#[derive(sqlx::FromRow)]
+struct JobRow {
+    id: Uuid,
+    output_bucket_path: String,
+    status: String,
+    video_id: Uuid,
+    error: Option<String>,
+    worker_id: Option<String>,
+    lease_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    version: i64,
+    idempotency_key: Option<String>,
+    attempts: i32,
+    max_attempts: i32,
+    next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    attempt_errors: String,
+    checkpoints: String,
+    source_message: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct JobWithVideoRow {
+    #[sqlx(flatten)]
+    job: JobRow,
+    video_resource_id: String,
+    video_file_path: String,
+    video_created_at: chrono::DateTime<chrono::Utc>,
+}
+
 type VideoRow = (Uuid, String, String, chrono::DateTime<chrono::Utc>);
 
 // Queries SQL como constantes
-const INSERT_JOB_QUERY: &str = "INSERT INTO jobs (id, output_bucket_path, status, video_id, error, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7)";
+const INSERT_JOB_QUERY: &str = r#"
+    INSERT INTO jobs (
+        id, output_bucket_path, status, video_id, error, worker_id, lease_expires_at, version,
+        idempotency_key, attempts, max_attempts, next_attempt_at, attempt_errors, checkpoints,
+        source_message, created_at, updated_at
+    )
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+"#;
+
+const FIND_JOB_QUERY: &str = r#"
+    SELECT
+        id, output_bucket_path, status, video_id, error, worker_id, lease_expires_at, version,
+        idempotency_key, attempts, max_attempts, next_attempt_at, attempt_errors, checkpoints,
+        source_message, created_at, updated_at
+    FROM jobs
+    WHERE id = $1
+"#;
+
+const FIND_JOBS_BY_VIDEO_QUERY: &str = r#"
+    SELECT
+        id, output_bucket_path, status, video_id, error, worker_id, lease_expires_at, version,
+        idempotency_key, attempts, max_attempts, next_attempt_at, attempt_errors, checkpoints,
+        source_message, created_at, updated_at
+    FROM jobs
+    WHERE video_id = $1
+    ORDER BY created_at DESC, id DESC
+"#;
+
+// Mesma condição do índice único parcial idx_jobs_idempotency_key
+const FIND_ACTIVE_JOB_BY_KEY_QUERY: &str =
+    "SELECT id FROM jobs WHERE idempotency_key = $1 AND status <> $2";
+
+const RELEASE_IDEMPOTENCY_KEY_QUERY: &str =
+    "UPDATE jobs SET idempotency_key = NULL WHERE idempotency_key = $1 AND status <> $2";
+
+const DELETE_JOB_QUERY: &str = "DELETE FROM jobs WHERE id = $1 RETURNING id";
 
-const FIND_JOB_QUERY: &str = "SELECT id, output_bucket_path, status, video_id, error, created_at, updated_at FROM jobs WHERE id = $1";
+const FIND_JOB_VERSION_QUERY: &str = "SELECT version FROM jobs WHERE id = $1";
 
 const FIND_VIDEO_QUERY: &str =
     "SELECT id, resource_id, file_path, created_at FROM videos WHERE id = $1";
 
-const UPDATE_JOB_QUERY: &str = "UPDATE jobs SET output_bucket_path = $1, status = $2, error = $3, updated_at = $4 WHERE id = $5";
+// Só atualiza se a versão não mudou desde que o job foi carregado
+const UPDATE_JOB_QUERY: &str = r#"
+    UPDATE jobs
+    SET output_bucket_path = $1, status = $2, error = $3, worker_id = $4, lease_expires_at = $5,
+        attempts = $6, next_attempt_at = $7, attempt_errors = $8, checkpoints = $9,
+        updated_at = $10, version = version + 1
+    WHERE id = $11 AND version = $12
+    RETURNING version
+"#;
+
+// Move o job pendente mais antigo para downloading em um único UPDATE, iniciando uma
+// nova tentativa como `Job::start`. No Postgres,
+// FOR UPDATE SKIP LOCKED faz workers concorrentes pularem a linha já reivindicada
+const CLAIM_NEXT_JOB_POSTGRES_QUERY: &str = r#"
+    UPDATE jobs
+    SET status = $1, worker_id = $2, lease_expires_at = $3, updated_at = $4, version = version + 1,
+        attempts = attempts + 1, next_attempt_at = NULL
+    WHERE id = (
+        SELECT id FROM jobs
+        WHERE status = $5
+        ORDER BY created_at, id
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+    )
+    RETURNING id
+"#;
+
+// O SQLite tem um único escritor, então o UPDATE com subquery já é atômico
+const CLAIM_NEXT_JOB_SQLITE_QUERY: &str = r#"
+    UPDATE jobs
+    SET status = $1, worker_id = $2, lease_expires_at = $3, updated_at = $4, version = version + 1,
+        attempts = attempts + 1, next_attempt_at = NULL
+    WHERE id = (
+        SELECT id FROM jobs
+        WHERE status = $5
+        ORDER BY created_at, id
+        LIMIT 1
+    )
+    RETURNING id
+"#;
+
+// A versão não muda: o worker segue gravando o job com a versão que carregou
+const RENEW_LEASE_QUERY: &str = r#"
+    UPDATE jobs
+    SET lease_expires_at = $1
+    WHERE id = $2 AND worker_id = $3
+    RETURNING id
+"#;
+
+// Filtros nulos são ignorados, então a mesma query atende Postgres e SQLite
+const LIST_JOBS_QUERY: &str = r#"
+    SELECT
+        j.id, j.output_bucket_path, j.status, j.video_id, j.error, j.worker_id,
+        j.lease_expires_at, j.version, j.idempotency_key, j.attempts, j.max_attempts,
+        j.next_attempt_at, j.attempt_errors, j.checkpoints, j.source_message, j.created_at,
+        j.updated_at,
+        v.resource_id AS video_resource_id, v.file_path AS video_file_path,
+        v.created_at AS video_created_at
+    FROM jobs j
+    JOIN videos v ON v.id = j.video_id
+    WHERE ($1 IS NULL OR j.status = $1)
+      AND ($2 IS NULL OR j.video_id = $2)
+      AND ($3 IS NULL OR j.created_at >= $3)
+      AND ($4 IS NULL OR j.created_at < $4)
+      AND ($5 IS NULL OR j.updated_at >= $5)
+      AND ($6 IS NULL OR j.updated_at < $6)
+      AND ($7 IS NULL OR j.next_attempt_at < $7)
+      AND ($8 IS NULL OR j.lease_expires_at < $8)
+      AND ($9 = 0 OR j.lease_expires_at IS NULL)
+    ORDER BY j.created_at DESC, j.id DESC
+    LIMIT $10 OFFSET $11
+"#;
+
+/// Filtros da listagem de jobs; intervalos de data são [after, before)
+#[derive(Debug, Clone, Default)]
+pub struct JobFilter {
+    pub status: Option<JobStatus>,
+    pub video_id: Option<Uuid>,
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Jobs cuja próxima tentativa está agendada antes deste instante
+    pub next_attempt_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Jobs cuja reivindicação expirou antes deste instante; jobs sem lease
+    /// não atendem o filtro
+    pub lease_expired_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Apenas jobs sem lease
+    pub without_lease: bool,
+}
+
+/// Queries do repositório sobre uma conexão explícita, compartilhadas entre
+/// o pool (Repository) e as transações (UnitOfWork)
+#[allow(async_fn_in_trait)]
+pub trait JobQueries<DB>
+where
+    DB: sqlx::Database,
+{
+    async fn insert_on(conn: &mut DB::Connection, item: &Job) -> Result<Job, JobRepositoryError>;
+    async fn find_on(conn: &mut DB::Connection, id: &Uuid) -> Result<Job, JobRepositoryError>;
+    async fn find_by_video_id_on(
+        conn: &mut DB::Connection,
+        video_id: &Uuid,
+    ) -> Result<Vec<Job>, JobRepositoryError>;
+    async fn list_on(
+        conn: &mut DB::Connection,
+        filter: &JobFilter,
+        pagination: Pagination,
+    ) -> Result<Page<Job>, JobRepositoryError>;
+    async fn delete_on(conn: &mut DB::Connection, id: &Uuid) -> Result<(), JobRepositoryError>;
+    async fn find_active_by_key_on(
+        conn: &mut DB::Connection,
+        key: &str,
+    ) -> Result<Option<Job>, JobRepositoryError>;
+    async fn release_idempotency_key_on(
+        conn: &mut DB::Connection,
+        key: &str,
+    ) -> Result<(), JobRepositoryError>;
+    async fn update_on(conn: &mut DB::Connection, item: &Job) -> Result<Job, JobRepositoryError>;
+}
 
 pub struct JobRepository<DB>
 where
@@ -36,6 +215,17 @@ where
     pub db: Database<DB>,
 }
 
+impl<DB> Clone for JobRepository<DB>
+where
+    DB: sqlx::Database,
+{
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+        }
+    }
+}
+
 impl<DB> JobRepository<DB>
 where
     DB: sqlx::Database,
@@ -44,23 +234,54 @@ where
         Self { db }
     }
 
-    /// Mapeia uma JobRow para um Job
-    fn map_job_from_row(row: JobRow, video: Arc<Video>) -> Job {
-        Job {
-            id: row.0,
-            output_bucket_path: row.1,
-            status: row.2,
+    /// Mapeia uma JobRow para um Job, convertendo a coluna status para JobStatus
+    /// e o histórico de erros e os checkpoints de JSON
+    fn map_job_from_row(row: JobRow, video: Arc<Video>) -> Result<Job, JobRepositoryError> {
+        let status = row
+            .status
+            .parse::<JobStatus>()
+            .map_err(|e| JobRepositoryError::Decode(e.to_string()))?;
+        let attempt_errors = serde_json::from_str(&row.attempt_errors)
+            .map_err(|e| JobRepositoryError::Decode(format!("attempt_errors: {}", e)))?;
+        let checkpoints = serde_json::from_str(&row.checkpoints)
+            .map_err(|e| JobRepositoryError::Decode(format!("checkpoints: {}", e)))?;
+
+        Ok(Job {
+            id: row.id,
+            output_bucket_path: row.output_bucket_path,
+            status,
             video,
-            video_id: row.3,
-            error: row.4,
-            created_at: row.5,
-            updated_at: row.6,
-        }
+            video_id: row.video_id,
+            error: row.error,
+            worker_id: row.worker_id,
+            lease_expires_at: row.lease_expires_at,
+            version: row.version,
+            idempotency_key: row.idempotency_key,
+            attempts: row.attempts,
+            max_attempts: row.max_attempts,
+            next_attempt_at: row.next_attempt_at,
+            attempt_errors,
+            checkpoints,
+            source_message: row.source_message,
+            events: Vec::new(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+
+    fn encode_attempt_errors(job: &Job) -> Result<String, JobRepositoryError> {
+        serde_json::to_string(&job.attempt_errors)
+            .map_err(|e| JobRepositoryError::Decode(format!("attempt_errors: {}", e)))
+    }
+
+    fn encode_checkpoints(job: &Job) -> Result<String, JobRepositoryError> {
+        serde_json::to_string(&job.checkpoints)
+            .map_err(|e| JobRepositoryError::Decode(format!("checkpoints: {}", e)))
     }
 }
 
 // Trait bounds organizados por categoria para melhor legibilidade
-impl<DB> Repository<Job> for JobRepository<DB>
+impl<DB> JobQueries<DB> for JobRepository<DB>
 where
     DB: sqlx::Database,
     // Suporte aos tipos usados nas queries
@@ -69,6 +290,10 @@ where
     for<'q> Option<String>: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
     for<'q> chrono::DateTime<chrono::Utc>:
         sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    for<'q> Option<Uuid>: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    for<'q> Option<chrono::DateTime<chrono::Utc>>: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    for<'q> i64: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    for<'q> i32: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
     // Suporte a referências nas queries
     for<'q> &'q Uuid: sqlx::Encode<'q, DB>,
     for<'q> &'q String: sqlx::Encode<'q, DB>,
@@ -77,44 +302,62 @@ where
     // Suporte aos argumentos e executor do sqlx
     for<'q> <DB as sqlx::Database>::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
     for<'c> &'c mut <DB as sqlx::Database>::Connection: sqlx::Executor<'c, Database = DB>,
-    // Suporte a indexação de colunas
+    // Suporte a indexação de colunas por posição e por nome
     usize: sqlx::ColumnIndex<DB::Row>,
+    for<'r> &'r str: sqlx::ColumnIndex<DB::Row>,
+    // Histórico de status gravado junto com o job
+    JobEventRepository<DB>: JobEventQueries<DB>,
 {
-    type Error = JobRepositoryError;
+    /// Insere um novo job no banco de dados com os seus eventos pendentes,
+    /// rejeitando entidades inválidas
+    async fn insert_on(conn: &mut DB::Connection, item: &Job) -> Result<Job, JobRepositoryError> {
+        item.validate()?;
 
-    /// Insere um novo job no banco de dados
-    async fn insert(&self, item: &Job) -> Result<Job, Self::Error> {
         sqlx::query(INSERT_JOB_QUERY)
             .bind(item.id)
             .bind(&item.output_bucket_path)
-            .bind(&item.status)
+            .bind(item.status.to_string())
             .bind(item.video_id)
             .bind(&item.error)
+            .bind(&item.worker_id)
+            .bind(item.lease_expires_at)
+            .bind(item.version)
+            .bind(&item.idempotency_key)
+            .bind(item.attempts)
+            .bind(item.max_attempts)
+            .bind(item.next_attempt_at)
+            .bind(Self::encode_attempt_errors(item)?)
+            .bind(Self::encode_checkpoints(item)?)
+            .bind(&item.source_message)
             .bind(item.created_at)
             .bind(item.updated_at)
-            .execute(&self.db.conn)
-            .await
-            .map_err(|e| JobRepositoryError(e.to_string()))?;
+            .execute(&mut *conn)
+            .await?;
+
+        JobEventRepository::<DB>::append_on(conn, &item.events).await?;
 
-        Ok(item.clone())
+        Ok(Job {
+            events: Vec::new(),
+            ..item.clone()
+        })
     }
 
     /// Busca um job por ID, carregando o vídeo associado
-    async fn find(&self, id: &Uuid) -> Result<Job, Self::Error> {
+    async fn find_on(conn: &mut DB::Connection, id: &Uuid) -> Result<Job, JobRepositoryError> {
         // Busca o job
         let job_row = sqlx::query_as::<_, JobRow>(FIND_JOB_QUERY)
             .bind(id)
-            .fetch_one(&self.db.conn)
-            .await
-            .map_err(|e| JobRepositoryError(e.to_string()))?;
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| JobRepositoryError::not_found("job", id))?;
 
         // Busca o vídeo associado ao job
-        let video_id = job_row.3;
+        let video_id = job_row.video_id;
         let video_row = sqlx::query_as::<_, VideoRow>(FIND_VIDEO_QUERY)
             .bind(video_id)
-            .fetch_one(&self.db.conn)
-            .await
-            .map_err(|e| JobRepositoryError(e.to_string()))?;
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| JobRepositoryError::not_found("video", video_id))?;
 
         let video = Arc::new(Video {
             id: video_row.0,
@@ -125,22 +368,402 @@ where
         });
 
         // Monta o Job com o vídeo carregado
-        Ok(Self::map_job_from_row(job_row, video))
+        Self::map_job_from_row(job_row, video)
     }
 
-    /// Atualiza um job existente (status, error, updated_at)
-    async fn update(&self, item: &Job) -> Result<Job, Self::Error> {
-        sqlx::query(UPDATE_JOB_QUERY)
+    /// Busca os jobs do vídeo, dos mais recentes para os mais antigos
+    async fn find_by_video_id_on(
+        conn: &mut DB::Connection,
+        video_id: &Uuid,
+    ) -> Result<Vec<Job>, JobRepositoryError> {
+        let job_rows = sqlx::query_as::<_, JobRow>(FIND_JOBS_BY_VIDEO_QUERY)
+            .bind(video_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        if job_rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Todos os jobs compartilham o mesmo vídeo
+        let video_row = sqlx::query_as::<_, VideoRow>(FIND_VIDEO_QUERY)
+            .bind(video_id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| JobRepositoryError::not_found("video", video_id))?;
+
+        let video = Arc::new(Video {
+            id: video_row.0,
+            resource_id: video_row.1,
+            file_path: video_row.2,
+            created_at: video_row.3,
+            jobs: Vec::new(),
+        });
+
+        job_rows
+            .into_iter()
+            .map(|row| Self::map_job_from_row(row, Arc::clone(&video)))
+            .collect()
+    }
+
+    /// Lista jobs com seus vídeos, dos mais recentes para os mais antigos
+    async fn list_on(
+        conn: &mut DB::Connection,
+        filter: &JobFilter,
+        pagination: Pagination,
+    ) -> Result<Page<Job>, JobRepositoryError> {
+        let rows = sqlx::query_as::<_, JobWithVideoRow>(LIST_JOBS_QUERY)
+            .bind(filter.status.map(|s| s.to_string()))
+            .bind(filter.video_id)
+            .bind(filter.created_after)
+            .bind(filter.created_before)
+            .bind(filter.updated_after)
+            .bind(filter.updated_before)
+            .bind(filter.next_attempt_before)
+            .bind(filter.lease_expired_before)
+            // Como inteiro, para não exigir suporte a bool de todos os bancos
+            .bind(i32::from(filter.without_lease))
+            .bind(i64::from(pagination.limit) + 1)
+            .bind(i64::from(pagination.offset))
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let jobs = rows
+            .into_iter()
+            .map(|row| {
+                let video = Arc::new(Video {
+                    id: row.job.video_id,
+                    resource_id: row.video_resource_id,
+                    file_path: row.video_file_path,
+                    created_at: row.video_created_at,
+                    jobs: Vec::new(),
+                });
+
+                Self::map_job_from_row(row.job, video)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Page::from_overfetch(jobs, pagination))
+    }
+
+    /// Atualiza um job existente (status, erros, tentativas, checkpoints, lease) se a versão no
+    /// banco ainda for a mesma com que o job foi carregado, gravando os eventos
+    /// pendentes e retornando o job com a nova versão. Retorna VersionConflict se outro processo alterou o
+    /// job antes, e NotFound se o job não existir mais
+    async fn update_on(conn: &mut DB::Connection, item: &Job) -> Result<Job, JobRepositoryError> {
+        let updated = sqlx::query_as::<_, (i64,)>(UPDATE_JOB_QUERY)
             .bind(&item.output_bucket_path)
-            .bind(&item.status)
+            .bind(item.status.to_string())
             .bind(&item.error)
+            .bind(&item.worker_id)
+            .bind(item.lease_expires_at)
+            .bind(item.attempts)
+            .bind(item.next_attempt_at)
+            .bind(Self::encode_attempt_errors(item)?)
+            .bind(Self::encode_checkpoints(item)?)
             .bind(item.updated_at)
             .bind(item.id)
-            .execute(&self.db.conn)
+            .bind(item.version)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        if let Some((version,)) = updated {
+            JobEventRepository::<DB>::append_on(conn, &item.events).await?;
+
+            return Ok(Job {
+                version,
+                events: Vec::new(),
+                ..item.clone()
+            });
+        }
+
+        // Nenhuma linha atualizada: o job foi removido ou mudou de versão
+        let current = sqlx::query_as::<_, (i64,)>(FIND_JOB_VERSION_QUERY)
+            .bind(item.id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        Err(match current {
+            Some(_) => JobRepositoryError::VersionConflict {
+                entity: "job",
+                key: item.id.to_string(),
+                version: item.version,
+            },
+            None => JobRepositoryError::not_found("job", item.id),
+        })
+    }
+
+    /// Remove o job, retornando NotFound se ele não existir
+    async fn delete_on(conn: &mut DB::Connection, id: &Uuid) -> Result<(), JobRepositoryError> {
+        sqlx::query_as::<_, (Uuid,)>(DELETE_JOB_QUERY)
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| JobRepositoryError::not_found("job", id))?;
+
+        Ok(())
+    }
+
+    /// Busca o job não falho que detém a chave de idempotência
+    async fn find_active_by_key_on(
+        conn: &mut DB::Connection,
+        key: &str,
+    ) -> Result<Option<Job>, JobRepositoryError> {
+        let active = sqlx::query_as::<_, (Uuid,)>(FIND_ACTIVE_JOB_BY_KEY_QUERY)
+            .bind(key.to_string())
+            .bind(JobStatus::Failed.to_string())
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        match active {
+            Some((id,)) => Self::find_on(conn, &id).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove a chave do job não falho que a detém, liberando-a para um novo job
+    async fn release_idempotency_key_on(
+        conn: &mut DB::Connection,
+        key: &str,
+    ) -> Result<(), JobRepositoryError> {
+        sqlx::query(RELEASE_IDEMPOTENCY_KEY_QUERY)
+            .bind(key.to_string())
+            .bind(JobStatus::Failed.to_string())
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<DB> Repository<Job> for JobRepository<DB>
+where
+    DB: sqlx::Database,
+    Self: JobQueries<DB>,
+{
+    type Error = JobRepositoryError;
+    type Filter = JobFilter;
+
+    /// O job e os seus eventos são gravados na mesma transação
+    async fn insert(&self, item: &Job) -> Result<Job, Self::Error> {
+        let mut uow = self.db.begin().await?;
+        let job = Self::insert_on(uow.connection(), item).await?;
+        uow.commit().await?;
+
+        Ok(job)
+    }
+
+    async fn find(&self, id: &Uuid) -> Result<Job, Self::Error> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::find_on(&mut conn, id).await
+    }
+
+    async fn list(
+        &self,
+        filter: &JobFilter,
+        pagination: Pagination,
+    ) -> Result<Page<Job>, Self::Error> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::list_on(&mut conn, filter, pagination).await
+    }
+
+    /// O job e os seus eventos são gravados na mesma transação
+    async fn update(&self, item: &Job) -> Result<Job, Self::Error> {
+        let mut uow = self.db.begin().await?;
+        let job = Self::update_on(uow.connection(), item).await?;
+        uow.commit().await?;
+
+        Ok(job)
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), Self::Error> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::delete_on(&mut conn, id).await
+    }
+}
+
+impl<DB> JobRepository<DB>
+where
+    DB: sqlx::Database,
+    Self: JobQueries<DB>,
+{
+    /// Retorna os jobs do vídeo, dos mais recentes para os mais antigos
+    pub async fn find_by_video_id(&self, video_id: &Uuid) -> Result<Vec<Job>, JobRepositoryError> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::find_by_video_id_on(&mut conn, video_id).await
+    }
+}
+
+impl<DB> JobRepository<DB>
+where
+    DB: sqlx::Database,
+    Self: JobQueries<DB>,
+    JobEventRepository<DB>: JobEventQueries<DB>,
+    for<'q> String: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    for<'q> chrono::DateTime<chrono::Utc>: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    for<'q> Uuid: sqlx::Encode<'q, DB> + sqlx::Decode<'q, DB> + sqlx::Type<DB>,
+    for<'q> <DB as sqlx::Database>::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+    for<'c> &'c mut <DB as sqlx::Database>::Connection: sqlx::Executor<'c, Database = DB>,
+    usize: sqlx::ColumnIndex<DB::Row>,
+{
+    /// Executa a query de reivindicação do banco e carrega o job reivindicado,
+    /// gravando o evento da transição na mesma transação
+    async fn claim_next_with(
+        &self,
+        query: &'static str,
+        worker_id: &str,
+        lease: chrono::Duration,
+    ) -> Result<Option<Job>, JobRepositoryError> {
+        let now = chrono::Utc::now();
+        let mut uow = self.db.begin().await?;
+
+        let claimed = sqlx::query_as::<_, (Uuid,)>(query)
+            .bind(JobStatus::Downloading.to_string())
+            .bind(worker_id.to_string())
+            .bind(now + lease)
+            .bind(now)
+            .bind(JobStatus::Pending.to_string())
+            .fetch_optional(&mut *uow.connection())
+            .await?;
+
+        let Some((id,)) = claimed else {
+            return Ok(None);
+        };
+
+        let job = Self::find_on(uow.connection(), &id).await?;
+
+        // O UPDATE não devolve o updated_at anterior, então o estágio pendente
+        // é medido a partir do último evento do job
+        let previous = JobEventRepository::<DB>::latest_on(uow.connection(), &id).await?;
+        let mut event = JobEvent {
+            worker_id: Some(worker_id.to_string()),
+            ..JobEvent::new(id, Some(JobStatus::Pending), JobStatus::Downloading, now)
+        };
+        if let Some(previous) = previous {
+            event = event.with_stage_started_at(previous.occurred_at);
+        }
+        JobEventRepository::<DB>::append_on(uow.connection(), &[event]).await?;
+
+        uow.commit().await?;
+
+        Ok(Some(job))
+    }
+
+    /// Grava a nova expiração da reivindicação se o job ainda é do worker
+    async fn renew_lease_on_db(
+        &self,
+        job_id: &Uuid,
+        worker_id: &str,
+        lease_expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), JobRepositoryError> {
+        let mut conn = self.db.conn.acquire().await?;
+
+        sqlx::query_as::<_, (Uuid,)>(RENEW_LEASE_QUERY)
+            .bind(lease_expires_at)
+            .bind(*job_id)
+            .bind(worker_id.to_string())
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| JobRepositoryError::not_found("job", job_id))?;
+
+        Ok(())
+    }
+}
+
+impl<DB> JobCreator for JobRepository<DB>
+where
+    DB: sqlx::Database,
+    Self: JobQueries<DB>,
+    VideoRepository<DB>: VideoQueries<DB>,
+{
+    /// Grava o vídeo e o job na mesma transação
+    async fn create_with_video(&self, job: &Job) -> Result<Job, JobRepositoryError> {
+        let mut uow = self.db.begin().await?;
+
+        uow.insert_video(&job.video).await?;
+        let job = uow.insert_job(job).await?;
+
+        uow.commit().await?;
+
+        Ok(job)
+    }
+
+    async fn submit(&self, job: &Job, force: bool) -> Result<Submission, JobRepositoryError> {
+        let Some(key) = job.idempotency_key.as_deref() else {
+            return self.create_with_video(job).await.map(Submission::Created);
+        };
+
+        let mut uow = self.db.begin().await?;
+
+        if force {
+            uow.release_idempotency_key(key).await?;
+        } else if let Some(existing) = uow.find_active_job_by_key(key).await? {
+            uow.rollback().await?;
+            return Ok(Submission::Existing(existing));
+        }
+
+        uow.insert_video(&job.video).await?;
+
+        let created = match uow.insert_job(job).await {
+            Ok(created) => created,
+            // Uma submissão concorrente gravou a mesma chave primeiro
+            Err(JobRepositoryError::Conflict(message)) if !force => {
+                uow.rollback().await?;
+
+                let mut conn = self.db.conn.acquire().await?;
+                return match Self::find_active_by_key_on(&mut conn, key).await? {
+                    Some(existing) => Ok(Submission::Existing(existing)),
+                    None => Err(JobRepositoryError::Conflict(message)),
+                };
+            }
+            Err(e) => return Err(e),
+        };
+
+        uow.commit().await?;
+
+        Ok(Submission::Created(created))
+    }
+}
+
+impl JobClaimer for JobRepository<Postgres> {
+    async fn claim_next(
+        &self,
+        worker_id: &str,
+        lease: chrono::Duration,
+    ) -> Result<Option<Job>, JobRepositoryError> {
+        self.claim_next_with(CLAIM_NEXT_JOB_POSTGRES_QUERY, worker_id, lease)
+            .await
+    }
+
+    async fn renew_lease(
+        &self,
+        job_id: &Uuid,
+        worker_id: &str,
+        lease_expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), JobRepositoryError> {
+        self.renew_lease_on_db(job_id, worker_id, lease_expires_at)
             .await
-            .map_err(|e| JobRepositoryError(e.to_string()))?;
+    }
+}
 
-        Ok(item.clone())
+impl JobClaimer for JobRepository<Sqlite> {
+    async fn claim_next(
+        &self,
+        worker_id: &str,
+        lease: chrono::Duration,
+    ) -> Result<Option<Job>, JobRepositoryError> {
+        self.claim_next_with(CLAIM_NEXT_JOB_SQLITE_QUERY, worker_id, lease)
+            .await
+    }
+
+    async fn renew_lease(
+        &self,
+        job_id: &Uuid,
+        worker_id: &str,
+        lease_expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), JobRepositoryError> {
+        self.renew_lease_on_db(job_id, worker_id, lease_expires_at)
+            .await
     }
 }
 
@@ -150,8 +773,8 @@ mod tests {
     use std::{env, sync::Arc};
 
     use crate::{
-        application::Repository,
-        domain::{Job, Video},
+        application::{JobClaimer, JobCreator, JobFilter, Pagination, Repository, RepositoryError},
+        domain::{Job, JobStatus, Video},
         framework::Database,
     };
 
@@ -186,7 +809,8 @@ mod tests {
         // Criar e inserir job
         let job_repo = super::JobRepository { db };
         let video_arc = Arc::new(new_video);
-        let new_job = Job::new("/output/path".to_string(), "pending".to_string(), video_arc);
+        let mut new_job = Job::new("/output/path".to_string(), video_arc);
+        new_job.source_message = Some(r#"{"resource_id":"resource_456"}"#.to_string());
 
         let inserted_job = job_repo
             .insert(&new_job)
@@ -205,6 +829,7 @@ mod tests {
         assert_eq!(found_job.output_bucket_path, new_job.output_bucket_path);
         assert_eq!(found_job.status, new_job.status);
         assert_eq!(found_job.video.id, new_job.video.id);
+        assert_eq!(found_job.source_message, new_job.source_message);
     }
 
     #[tokio::test]
@@ -229,11 +854,7 @@ mod tests {
         // Criar e inserir job
         let job_repo = super::JobRepository { db };
         let video_arc = Arc::new(new_video);
-        let mut new_job = Job::new(
-            "/output/path2".to_string(),
-            "pending".to_string(),
-            video_arc,
-        );
+        let mut new_job = Job::new("/output/path2".to_string(), video_arc);
 
         job_repo
             .insert(&new_job)
@@ -241,15 +862,16 @@ mod tests {
             .expect("Failed to insert job");
 
         // Atualizar job
-        new_job.status = "completed".to_string();
-        new_job.updated_at = chrono::Utc::now();
+        new_job
+            .transition_to(JobStatus::Downloading)
+            .expect("Failed to transition job");
 
         let updated_job = job_repo
             .update(&new_job)
             .await
             .expect("Failed to update job");
 
-        assert_eq!(updated_job.status, "completed");
+        assert_eq!(updated_job.status, JobStatus::Downloading);
 
         // Verificar se foi atualizado no banco
         let found_job = job_repo
@@ -257,6 +879,339 @@ mod tests {
             .await
             .expect("Failed to find updated job");
 
-        assert_eq!(found_job.status, "completed");
+        assert_eq!(found_job.status, JobStatus::Downloading);
+    }
+
+    #[tokio::test]
+    async fn test_job_repository_maps_database_errors() {
+        let db = setup_test_db().await;
+        let job_repo = super::JobRepository::new(db.clone());
+        let video_repo = super::super::VideoRepository::new(db);
+
+        let video = Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string());
+        let job = Job::new("/output/path".to_string(), Arc::new(video.clone()));
+
+        // O vídeo ainda não foi inserido
+        let result = job_repo.insert(&job).await;
+        assert!(matches!(
+            result,
+            Err(RepositoryError::ForeignKeyViolation(_))
+        ));
+
+        video_repo
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+        let result = video_repo.insert(&video).await;
+        assert!(matches!(result, Err(RepositoryError::Conflict(_))));
+
+        let result = job_repo.find(&job.id).await;
+        assert!(matches!(
+            result,
+            Err(RepositoryError::NotFound { entity: "job", .. })
+        ));
+
+        let result = job_repo.update(&job).await;
+        assert!(result.is_err_and(|e| e.is_not_found() && !e.is_transient()));
+    }
+
+    #[tokio::test]
+    async fn test_job_repository_list_with_filters() {
+        let db = setup_test_db().await;
+        let job_repo = super::JobRepository::new(db.clone());
+        let video_repo = super::super::VideoRepository::new(db);
+
+        let video = Arc::new(Video::new(
+            "resource_123".to_string(),
+            "/path/to/video.mp4".to_string(),
+        ));
+        video_repo
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+
+        let mut failed = Job::new("/output/failed".to_string(), Arc::clone(&video));
+        failed.fail("encode failed").expect("Failed to fail job");
+        let pending = Job::new("/output/pending".to_string(), Arc::clone(&video));
+
+        // Job antigo, fora do intervalo de um dia
+        let mut old_failed = Job::new("/output/old".to_string(), Arc::clone(&video));
+        old_failed
+            .fail("download failed")
+            .expect("Failed to fail job");
+        old_failed.created_at -= chrono::Duration::days(3);
+        old_failed.updated_at -= chrono::Duration::days(3);
+
+        for job in [&failed, &pending, &old_failed] {
+            job_repo.insert(job).await.expect("Failed to insert job");
+        }
+
+        // Todos os jobs que falharam no último dia
+        let filter = JobFilter {
+            status: Some(JobStatus::Failed),
+            updated_after: Some(chrono::Utc::now() - chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        let page = job_repo
+            .list(&filter, Pagination::default())
+            .await
+            .expect("Failed to list jobs");
+
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].id, failed.id);
+        assert_eq!(page.items[0].video.resource_id, "resource_123");
+        assert!(page.next.is_none());
+
+        let filter = JobFilter {
+            video_id: Some(video.id),
+            ..Default::default()
+        };
+        let page = job_repo
+            .list(&filter, Pagination::new(2, 0))
+            .await
+            .expect("Failed to list jobs");
+
+        // Mais recentes primeiro; o job antigo fica para a próxima página
+        assert_eq!(page.items.len(), 2);
+        assert!(page.items.iter().all(|j| j.id != old_failed.id));
+        assert_eq!(page.next, Some(Pagination::new(2, 2)));
+
+        let filter = JobFilter {
+            created_before: Some(chrono::Utc::now() - chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        let page = job_repo
+            .list(&filter, Pagination::default())
+            .await
+            .expect("Failed to list jobs");
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].id, old_failed.id);
+    }
+
+    #[tokio::test]
+    async fn test_job_repository_claim_next() {
+        let db = setup_test_db().await;
+        let job_repo = super::JobRepository::new(db.clone());
+        let video_repo = super::super::VideoRepository::new(db);
+
+        let lease = chrono::Duration::minutes(5);
+        let claimed = job_repo
+            .claim_next("worker-1", lease)
+            .await
+            .expect("Failed to claim job");
+        assert!(claimed.is_none());
+
+        let video = Arc::new(Video::new(
+            "resource_123".to_string(),
+            "/path/to/video.mp4".to_string(),
+        ));
+        video_repo
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+
+        let mut oldest = Job::new("/output/oldest".to_string(), Arc::clone(&video));
+        oldest.created_at -= chrono::Duration::minutes(1);
+        let newest = Job::new("/output/newest".to_string(), Arc::clone(&video));
+        job_repo
+            .insert(&newest)
+            .await
+            .expect("Failed to insert job");
+        job_repo
+            .insert(&oldest)
+            .await
+            .expect("Failed to insert job");
+
+        // O job mais antigo é reivindicado primeiro, e cada job só uma vez
+        let first = job_repo
+            .claim_next("worker-1", lease)
+            .await
+            .expect("Failed to claim job")
+            .expect("A pending job should be claimed");
+        assert_eq!(first.id, oldest.id);
+        assert_eq!(first.status, JobStatus::Downloading);
+        assert_eq!(first.worker_id.as_deref(), Some("worker-1"));
+        assert!(first.lease_expires_at.unwrap() > chrono::Utc::now());
+        assert_eq!(first.video.id, video.id);
+
+        let second = job_repo
+            .claim_next("worker-2", lease)
+            .await
+            .expect("Failed to claim job")
+            .expect("A pending job should be claimed");
+        assert_eq!(second.id, newest.id);
+        assert_eq!(second.worker_id.as_deref(), Some("worker-2"));
+
+        let none = job_repo
+            .claim_next("worker-3", lease)
+            .await
+            .expect("Failed to claim job");
+        assert!(none.is_none());
+
+        // Só o worker dono do job renova a reivindicação, e a versão não muda
+        let renewed_until = first.lease_expires_at.unwrap() + lease;
+        job_repo
+            .renew_lease(&first.id, "worker-1", renewed_until)
+            .await
+            .expect("Failed to renew lease");
+        let renewed = job_repo.find(&first.id).await.expect("Failed to find job");
+        assert_eq!(renewed.lease_expires_at, Some(renewed_until));
+        assert_eq!(renewed.version, first.version);
+
+        let result = job_repo
+            .renew_lease(&first.id, "worker-2", renewed_until)
+            .await;
+        assert!(result.is_err_and(|e| e.is_not_found()));
+    }
+
+    #[tokio::test]
+    async fn test_job_repository_update_detects_version_conflict() {
+        let db = setup_test_db().await;
+        let job_repo = super::JobRepository::new(db.clone());
+        let video_repo = super::super::VideoRepository::new(db);
+
+        let video = Arc::new(Video::new(
+            "resource_123".to_string(),
+            "/path/to/video.mp4".to_string(),
+        ));
+        video_repo
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+
+        let job = Job::new("/output/path".to_string(), Arc::clone(&video));
+        job_repo.insert(&job).await.expect("Failed to insert job");
+
+        // Dois processos carregam a mesma versão do job
+        let mut completed = job_repo.find(&job.id).await.expect("Failed to find job");
+        let mut late_failure = completed.clone();
+        assert_eq!(completed.version, 0);
+
+        completed
+            .transition_to(JobStatus::Downloading)
+            .expect("Failed to transition job");
+        let completed = job_repo
+            .update(&completed)
+            .await
+            .expect("Failed to update job");
+        assert_eq!(completed.version, 1);
+
+        // A falha tardia não pode sobrescrever a atualização anterior
+        late_failure
+            .fail("late failure")
+            .expect("Failed to fail job");
+        let result = job_repo.update(&late_failure).await;
+        assert!(matches!(
+            result,
+            Err(RepositoryError::VersionConflict { version: 0, .. })
+        ));
+
+        let found_job = job_repo.find(&job.id).await.expect("Failed to find job");
+        assert_eq!(found_job.status, JobStatus::Downloading);
+        assert_eq!(found_job.error, None);
+        assert_eq!(found_job.version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_job_repository_find_by_video_id_latest_first() {
+        let db = setup_test_db().await;
+        let video_repo = super::super::VideoRepository::new(db.clone());
+        let job_repo = super::JobRepository::new(db);
+
+        let video = Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string());
+        video_repo
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+
+        let jobs = job_repo
+            .find_by_video_id(&video.id)
+            .await
+            .expect("Failed to find jobs");
+        assert!(jobs.is_empty());
+
+        let video = Arc::new(video);
+        let mut first = Job::new("/output/first".to_string(), Arc::clone(&video));
+        first.created_at -= chrono::Duration::minutes(1);
+        let second = Job::new("/output/second".to_string(), Arc::clone(&video));
+        job_repo.insert(&first).await.expect("Failed to insert job");
+        job_repo
+            .insert(&second)
+            .await
+            .expect("Failed to insert job");
+
+        let jobs = job_repo
+            .find_by_video_id(&video.id)
+            .await
+            .expect("Failed to find jobs");
+        let ids: Vec<_> = jobs.iter().map(|job| job.id).collect();
+        assert_eq!(ids, vec![second.id, first.id]);
+        assert!(
+            jobs.iter()
+                .all(|job| job.video.resource_id == "resource_123")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_job_repository_submit_is_idempotent() {
+        let db = setup_test_db().await;
+        let job_repo = super::JobRepository::new(db);
+
+        let new_job = || {
+            let video = Video::new("resource_123".to_string(), "videos/a.mp4".to_string());
+            let mut job = Job::new("/output/path".to_string(), Arc::new(video));
+            job.idempotency_key = Some(job.video.idempotency_key());
+            job
+        };
+
+        let first = job_repo
+            .submit(&new_job(), false)
+            .await
+            .expect("Failed to submit job");
+        assert!(first.is_created());
+        let first = first.into_job();
+
+        // A mesma submissão retorna o job existente sem criar outro vídeo
+        let duplicate = new_job();
+        let submission = job_repo
+            .submit(&duplicate, false)
+            .await
+            .expect("Failed to submit job");
+        assert!(!submission.is_created());
+        assert_eq!(submission.into_job().id, first.id);
+
+        let result = super::super::VideoRepository::new(job_repo.db.clone())
+            .find(&duplicate.video_id)
+            .await;
+        assert!(result.is_err_and(|e| e.is_not_found()));
+
+        // O índice único impede um segundo job não falho com a mesma chave
+        let result = job_repo.create_with_video(&new_job()).await;
+        assert!(matches!(result, Err(RepositoryError::Conflict(_))));
+
+        // Com force, a chave passa para o novo job
+        let forced = job_repo
+            .submit(&new_job(), true)
+            .await
+            .expect("Failed to submit job");
+        assert!(forced.is_created());
+        let mut forced = forced.into_job();
+
+        let previous = job_repo.find(&first.id).await.expect("Failed to find job");
+        assert_eq!(previous.idempotency_key, None);
+
+        // Um job falho libera a chave para uma nova submissão
+        forced.transition_to(JobStatus::Downloading).unwrap();
+        forced.fail("encode failed").unwrap();
+        job_repo
+            .update(&forced)
+            .await
+            .expect("Failed to update job");
+
+        let retried = job_repo
+            .submit(&new_job(), false)
+            .await
+            .expect("Failed to submit job");
+        assert!(retried.is_created());
     }
 }
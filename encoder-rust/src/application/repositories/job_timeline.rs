@@ -0,0 +1,11 @@
+use uuid::Uuid;
+
+use crate::{application::JobRepositoryError, domain::JobEvent};
+
+/// Consulta do histórico de status gravado em job_events
+#[allow(async_fn_in_trait)]
+pub trait JobTimeline: Send + Sync {
+    /// Eventos do job na ordem em que aconteceram, do evento de criação ao
+    /// mais recente. Retorna NotFound se o job não existir
+    async fn timeline(&self, job_id: &Uuid) -> Result<Vec<JobEvent>, JobRepositoryError>;
+}
@@ -0,0 +1,51 @@
+/// Paginação por offset usada nas listagens dos repositórios
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    pub limit: u32,
+    pub offset: u32,
+}
+
+impl Pagination {
+    pub const DEFAULT_LIMIT: u32 = 50;
+    pub const MAX_LIMIT: u32 = 500;
+
+    /// Cria a paginação limitando `limit` ao intervalo 1..=MAX_LIMIT
+    pub fn new(limit: u32, offset: u32) -> Self {
+        Pagination {
+            limit: limit.clamp(1, Self::MAX_LIMIT),
+            offset,
+        }
+    }
+
+    /// Página seguinte a esta
+    pub fn next(&self) -> Self {
+        Pagination::new(self.limit, self.offset.saturating_add(self.limit))
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Pagination::new(Self::DEFAULT_LIMIT, 0)
+    }
+}
+
+/// Uma página de resultados; `next` é `None` quando não há mais itens
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<Pagination>,
+}
+
+impl<T> Page<T> {
+    /// Monta a página a partir de uma consulta feita com `limit + 1` linhas,
+    /// usando a linha excedente apenas para saber se existe uma próxima página
+    pub fn from_overfetch(mut items: Vec<T>, pagination: Pagination) -> Self {
+        let has_more = items.len() > pagination.limit as usize;
+        items.truncate(pagination.limit as usize);
+
+        Page {
+            items,
+            next: has_more.then(|| pagination.next()),
+        }
+    }
+}
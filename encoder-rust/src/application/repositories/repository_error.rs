@@ -1,21 +1,176 @@
+use crate::domain::{JobStatusError, ValidationError};
+
+/// Erros dos repositórios, classificados a partir do `sqlx::Error` e dos
+/// códigos de erro do Postgres e do SQLite para que os chamadores possam
+/// decidir entre descartar, rejeitar ou tentar novamente
 #[derive(Debug)]
-pub struct VideoRepositoryError(pub String);
+pub enum RepositoryError {
+    /// Nenhum registro encontrado para a chave informada
+    NotFound { entity: &'static str, key: String },
+    /// Violação de unicidade (chave primária ou índice único)
+    Conflict(String),
+    /// O registro foi alterado por outro processo desde que foi carregado
+    VersionConflict {
+        entity: &'static str,
+        key: String,
+        version: i64,
+    },
+    /// Registro referencia uma entidade inexistente
+    ForeignKeyViolation(String),
+    /// Falha de conexão com o banco (rede, TLS, pool fechado)
+    Connection(String),
+    /// Pool sem conexões disponíveis ou banco ocupado/bloqueado
+    Timeout(String),
+    /// Falha de serialização ou deadlock: a transação foi desfeita e pode ser
+    /// repetida
+    Serialization(String),
+    /// Uma coluna não pôde ser convertida para o tipo do domínio
+    Decode(String),
+    /// A entidade não passou na validação do domínio
+    Validation(ValidationError),
+    /// A mudança de status pedida não é permitida pelo domínio
+    InvalidTransition(JobStatusError),
+    /// Qualquer outro erro retornado pelo banco
+    Database(String),
+}
 
-impl std::fmt::Display for VideoRepositoryError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "VideoRepository error: {}", self.0)
+// Os dois repositórios compartilham os mesmos modos de falha
+pub type VideoRepositoryError = RepositoryError;
+pub type JobRepositoryError = RepositoryError;
+
+// Códigos de erros transitórios: serialization_failure e deadlock_detected
+// no Postgres, SQLITE_BUSY e SQLITE_LOCKED (e seus códigos estendidos) no SQLite
+const POSTGRES_SERIALIZATION_CODES: [&str; 2] = ["40001", "40P01"];
+const SQLITE_BUSY: i64 = 5;
+const SQLITE_LOCKED: i64 = 6;
+
+impl RepositoryError {
+    pub fn not_found(entity: &'static str, key: impl ToString) -> Self {
+        RepositoryError::NotFound {
+            entity,
+            key: key.to_string(),
+        }
+    }
+
+    pub fn is_version_conflict(&self) -> bool {
+        matches!(self, RepositoryError::VersionConflict { .. })
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, RepositoryError::NotFound { .. })
+    }
+
+    /// Erros em que uma nova tentativa pode ter sucesso
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            RepositoryError::Connection(_)
+                | RepositoryError::Timeout(_)
+                | RepositoryError::Serialization(_)
+        )
+    }
+
+    fn from_database_error(e: &dyn sqlx::error::DatabaseError) -> Self {
+        let message = e.message().to_string();
+
+        match e.kind() {
+            sqlx::error::ErrorKind::UniqueViolation => return RepositoryError::Conflict(message),
+            sqlx::error::ErrorKind::ForeignKeyViolation => {
+                return RepositoryError::ForeignKeyViolation(message);
+            }
+            _ => {}
+        }
+
+        let code = e.code().unwrap_or_default();
+
+        if POSTGRES_SERIALIZATION_CODES.contains(&code.as_ref()) {
+            return RepositoryError::Serialization(message);
+        }
+
+        // Os códigos estendidos do SQLite carregam o código primário no byte menos significativo
+        if let Ok(code) = code.parse::<i64>()
+            && matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED)
+        {
+            return RepositoryError::Timeout(message);
+        }
+
+        RepositoryError::Database(message)
     }
 }
 
-impl std::error::Error for VideoRepositoryError {}
+impl From<sqlx::Error> for RepositoryError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            // Sem a entidade e a chave o erro não diria o que faltou: as queries
+            // usam fetch_optional e retornam `RepositoryError::not_found`
+            sqlx::Error::RowNotFound => RepositoryError::Database(e.to_string()),
+            sqlx::Error::Database(db) => Self::from_database_error(db.as_ref()),
+            sqlx::Error::PoolTimedOut => RepositoryError::Timeout(e.to_string()),
+            sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_)
+            | sqlx::Error::Protocol(_)
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed => RepositoryError::Connection(e.to_string()),
+            sqlx::Error::TypeNotFound { .. }
+            | sqlx::Error::ColumnIndexOutOfBounds { .. }
+            | sqlx::Error::ColumnNotFound(_)
+            | sqlx::Error::ColumnDecode { .. }
+            | sqlx::Error::Decode(_) => RepositoryError::Decode(e.to_string()),
+            e => RepositoryError::Database(e.to_string()),
+        }
+    }
+}
 
-#[derive(Debug)]
-pub struct JobRepositoryError(pub String);
+impl From<ValidationError> for RepositoryError {
+    fn from(e: ValidationError) -> Self {
+        RepositoryError::Validation(e)
+    }
+}
 
-impl std::fmt::Display for JobRepositoryError {
+impl From<JobStatusError> for RepositoryError {
+    fn from(e: JobStatusError) -> Self {
+        RepositoryError::InvalidTransition(e)
+    }
+}
+
+impl std::fmt::Display for RepositoryError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "JobRepository error: {}", self.0)
+        match self {
+            RepositoryError::NotFound { entity, key } => write!(f, "{} {} not found", entity, key),
+            RepositoryError::Conflict(message) => write!(f, "Conflict: {}", message),
+            RepositoryError::VersionConflict {
+                entity,
+                key,
+                version,
+            } => write!(
+                f,
+                "{} {} was modified concurrently (expected version {})",
+                entity, key, version
+            ),
+            RepositoryError::ForeignKeyViolation(message) => {
+                write!(f, "Foreign key violation: {}", message)
+            }
+            RepositoryError::Connection(message) => {
+                write!(f, "Database connection error: {}", message)
+            }
+            RepositoryError::Timeout(message) => write!(f, "Database timeout: {}", message),
+            RepositoryError::Serialization(message) => {
+                write!(f, "Serialization failure: {}", message)
+            }
+            RepositoryError::Decode(message) => write!(f, "Decode error: {}", message),
+            RepositoryError::Validation(e) => write!(f, "{}", e),
+            RepositoryError::InvalidTransition(e) => write!(f, "{}", e),
+            RepositoryError::Database(message) => write!(f, "Database error: {}", message),
+        }
     }
 }
 
-impl std::error::Error for JobRepositoryError {}
+impl std::error::Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RepositoryError::Validation(e) => Some(e),
+            RepositoryError::InvalidTransition(e) => Some(e),
+            _ => None,
+        }
+    }
+}
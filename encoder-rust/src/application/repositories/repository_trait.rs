@@ -1,13 +1,27 @@
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::application::{Page, Pagination};
+
+// Os traits assíncronos do crate só são implementados aqui dentro, então as
+// futures não precisam declarar o limite de Send
+#[allow(async_fn_in_trait)]
 pub trait Repository<T>: Send + Sync {
     type Error: Error + Send;
+    /// Filtros aceitos por `list` (todos opcionais; o Default não filtra nada)
+    type Filter: Default + Send + Sync;
 
     async fn insert(&self, item: &T) -> Result<T, Self::Error>;
     async fn find(&self, id: &Uuid) -> Result<T, Self::Error>;
+    async fn update(&self, item: &T) -> Result<T, Self::Error>;
+
+    /// Lista os itens que atendem ao filtro, dos mais recentes para os mais antigos
+    async fn list(
+        &self,
+        filter: &Self::Filter,
+        pagination: Pagination,
+    ) -> Result<Page<T>, Self::Error>;
 
-    async fn update(&self, _item: &T) -> Result<T, Self::Error> {
-        unimplemented!("update not implemented for this repository")
-    }
+    /// Remove o item; remover um vídeo remove também os seus jobs
+    async fn delete(&self, id: &Uuid) -> Result<(), Self::Error>;
 }
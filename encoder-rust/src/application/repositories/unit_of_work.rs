@@ -0,0 +1,128 @@
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        JobQueries, JobRepository, JobRepositoryError, VideoQueries, VideoRepository,
+        VideoRepositoryError,
+    },
+    domain::{Job, Video},
+    framework::database::UnitOfWork,
+};
+
+// Operações dos repositórios executadas dentro da transação da unidade de trabalho
+impl<DB> UnitOfWork<DB>
+where
+    DB: sqlx::Database,
+    VideoRepository<DB>: VideoQueries<DB>,
+    JobRepository<DB>: JobQueries<DB>,
+{
+    pub async fn insert_video(&mut self, video: &Video) -> Result<Video, VideoRepositoryError> {
+        VideoRepository::<DB>::insert_on(self.connection(), video).await
+    }
+
+    pub async fn find_video(&mut self, id: &Uuid) -> Result<Video, VideoRepositoryError> {
+        VideoRepository::<DB>::find_on(self.connection(), id).await
+    }
+
+    pub async fn insert_job(&mut self, job: &Job) -> Result<Job, JobRepositoryError> {
+        JobRepository::<DB>::insert_on(self.connection(), job).await
+    }
+
+    pub async fn find_job(&mut self, id: &Uuid) -> Result<Job, JobRepositoryError> {
+        JobRepository::<DB>::find_on(self.connection(), id).await
+    }
+
+    pub async fn update_job(&mut self, job: &Job) -> Result<Job, JobRepositoryError> {
+        JobRepository::<DB>::update_on(self.connection(), job).await
+    }
+
+    pub async fn find_active_job_by_key(
+        &mut self,
+        key: &str,
+    ) -> Result<Option<Job>, JobRepositoryError> {
+        JobRepository::<DB>::find_active_by_key_on(self.connection(), key).await
+    }
+
+    pub async fn release_idempotency_key(&mut self, key: &str) -> Result<(), JobRepositoryError> {
+        JobRepository::<DB>::release_idempotency_key_on(self.connection(), key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::Sqlite;
+    use std::{env, sync::Arc};
+
+    use crate::{
+        application::{JobRepository, Repository, VideoRepository},
+        domain::{Job, Video},
+        framework::Database,
+    };
+
+    async fn setup_test_db() -> Database<Sqlite> {
+        let database_url =
+            env::var("DATABASE_URL_TEST").unwrap_or_else(|_| "sqlite::memory:".to_string());
+
+        Database::<Sqlite>::new(database_url, Some(true))
+            .await
+            .expect("Failed to create test database connection")
+    }
+
+    fn new_video_and_job() -> (Video, Job) {
+        let video = Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string());
+        let job = Job::new("/output/path".to_string(), Arc::new(video.clone()));
+
+        (video, job)
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_commit_persists_video_and_job() {
+        let db = setup_test_db().await;
+        let (video, job) = new_video_and_job();
+
+        let mut uow = db.begin().await.expect("Failed to begin transaction");
+        uow.insert_video(&video)
+            .await
+            .expect("Failed to insert video");
+        uow.insert_job(&job).await.expect("Failed to insert job");
+
+        // Dentro da transação as escritas já são visíveis
+        let found_job = uow.find_job(&job.id).await.expect("Failed to find job");
+        assert_eq!(found_job.video.id, video.id);
+
+        uow.commit().await.expect("Failed to commit");
+
+        let found_video = VideoRepository::new(db.clone())
+            .find(&video.id)
+            .await
+            .expect("Failed to find video");
+        assert_eq!(found_video.jobs.len(), 1);
+        assert_eq!(found_video.jobs[0].id, job.id);
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_rollback_discards_writes() {
+        let db = setup_test_db().await;
+        let (video, job) = new_video_and_job();
+
+        let mut uow = db.begin().await.expect("Failed to begin transaction");
+        uow.insert_video(&video)
+            .await
+            .expect("Failed to insert video");
+        uow.insert_job(&job).await.expect("Failed to insert job");
+        uow.rollback().await.expect("Failed to roll back");
+
+        // Descartar sem commit também desfaz a transação
+        let mut uow = db.begin().await.expect("Failed to begin transaction");
+        uow.insert_video(&video)
+            .await
+            .expect("Failed to insert video");
+        drop(uow);
+
+        let video_result = VideoRepository::new(db.clone()).find(&video.id).await;
+        assert!(video_result.is_err_and(|e| e.is_not_found()));
+
+        let job_result = JobRepository::new(db).find(&job.id).await;
+        assert!(job_result.is_err_and(|e| e.is_not_found()));
+    }
+}
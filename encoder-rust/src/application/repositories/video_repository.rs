@@ -2,40 +2,112 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    application::{Repository, VideoRepositoryError},
-    domain::{Job, Video},
+    application::{Page, Pagination, Repository, VideoRepositoryError},
+    domain::{Job, JobStatus, Video},
     framework::Database,
 };
 
 // Type aliases para melhor legibilidade
 type VideoRow = (Uuid, String, String, chrono::DateTime<chrono::Utc>);
-type VideoWithJobsRow = (
-    Uuid,
-    String,
-    String,
-    chrono::DateTime<chrono::Utc>,
-    Option<Uuid>,
-    Option<String>,
-    Option<String>,
-    Option<Uuid>,
-    Option<String>,
-    Option<chrono::DateTime<chrono::Utc>>,
-    Option<chrono::DateTime<chrono::Utc>>,
-);
+
+// Linha do LEFT JOIN lida por nome de coluna; as colunas do job são nulas
+// quando o vídeo não tem jobs
+#[derive(sqlx::FromRow)]
+struct VideoWithJobsRow {
+    id: Uuid,
+    resource_id: String,
+    file_path: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    job_id: Option<Uuid>,
+    job_output_bucket_path: Option<String>,
+    job_status: Option<String>,
+    job_error: Option<String>,
+    job_worker_id: Option<String>,
+    job_lease_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    job_version: Option<i64>,
+    job_idempotency_key: Option<String>,
+    job_attempts: Option<i32>,
+    job_max_attempts: Option<i32>,
+    job_next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    job_attempt_errors: Option<String>,
+    job_checkpoints: Option<String>,
+    job_source_message: Option<String>,
+    job_created_at: Option<chrono::DateTime<chrono::Utc>>,
+    job_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
 
 // Queries SQL como constantes
 const INSERT_VIDEO_QUERY: &str =
     "INSERT INTO videos (id, resource_id, file_path, created_at) VALUES ($1, $2, $3, $4)";
 
-const FIND_VIDEO_WITH_JOBS_QUERY: &str = r#"
+const UPDATE_VIDEO_QUERY: &str =
+    "UPDATE videos SET resource_id = $1, file_path = $2 WHERE id = $3 RETURNING id";
+
+// Vídeos com os seus jobs em uma única query, filtrados por id ou por
+// resource_id (filtros nulos são ignorados), dos mais recentes para os mais antigos
+const FIND_VIDEOS_WITH_JOBS_QUERY: &str = r#"
     SELECT 
         v.id, v.resource_id, v.file_path, v.created_at,
-        j.id, j.output_bucket_path, j.status, j.video_id, j.error, j.created_at, j.updated_at
+        j.id AS job_id, j.output_bucket_path AS job_output_bucket_path, j.status AS job_status,
+        j.error AS job_error, j.worker_id AS job_worker_id,
+        j.lease_expires_at AS job_lease_expires_at, j.version AS job_version,
+        j.idempotency_key AS job_idempotency_key, j.attempts AS job_attempts,
+        j.max_attempts AS job_max_attempts, j.next_attempt_at AS job_next_attempt_at,
+        j.attempt_errors AS job_attempt_errors, j.checkpoints AS job_checkpoints,
+        j.source_message AS job_source_message,
+        j.created_at AS job_created_at, j.updated_at AS job_updated_at
     FROM videos v
     LEFT JOIN jobs j ON v.id = j.video_id
-    WHERE v.id = $1
+    WHERE ($1 IS NULL OR v.id = $1)
+      AND ($2 IS NULL OR v.resource_id = $2)
+    ORDER BY v.created_at DESC, v.id DESC, j.created_at DESC, j.id DESC
+"#;
+
+// Os jobs do vídeo são removidos pelo ON DELETE CASCADE
+const DELETE_VIDEO_QUERY: &str = "DELETE FROM videos WHERE id = $1 RETURNING id";
+
+const LIST_VIDEOS_QUERY: &str = r#"
+    SELECT id, resource_id, file_path, created_at
+    FROM videos
+    WHERE ($1 IS NULL OR resource_id = $1)
+    ORDER BY created_at DESC, id DESC
+    LIMIT $2 OFFSET $3
 "#;
 
+/// Filtros da listagem de vídeos
+#[derive(Debug, Clone, Default)]
+pub struct VideoFilter {
+    pub resource_id: Option<String>,
+}
+
+/// Queries do repositório sobre uma conexão explícita, compartilhadas entre
+/// o pool (Repository) e as transações (UnitOfWork)
+#[allow(async_fn_in_trait)]
+pub trait VideoQueries<DB>
+where
+    DB: sqlx::Database,
+{
+    async fn insert_on(
+        conn: &mut DB::Connection,
+        item: &Video,
+    ) -> Result<Video, VideoRepositoryError>;
+    async fn update_on(
+        conn: &mut DB::Connection,
+        item: &Video,
+    ) -> Result<Video, VideoRepositoryError>;
+    async fn find_on(conn: &mut DB::Connection, id: &Uuid) -> Result<Video, VideoRepositoryError>;
+    async fn find_by_resource_id_on(
+        conn: &mut DB::Connection,
+        resource_id: &str,
+    ) -> Result<Vec<Video>, VideoRepositoryError>;
+    async fn list_on(
+        conn: &mut DB::Connection,
+        filter: &VideoFilter,
+        pagination: Pagination,
+    ) -> Result<Page<Video>, VideoRepositoryError>;
+    async fn delete_on(conn: &mut DB::Connection, id: &Uuid) -> Result<(), VideoRepositoryError>;
+}
+
 pub struct VideoRepository<DB>
 where
     DB: sqlx::Database,
@@ -43,6 +115,17 @@ where
     pub db: Database<DB>,
 }
 
+impl<DB> Clone for VideoRepository<DB>
+where
+    DB: sqlx::Database,
+{
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+        }
+    }
+}
+
 impl<DB> VideoRepository<DB>
 where
     DB: sqlx::Database,
@@ -52,33 +135,108 @@ where
     }
 
     /// Mapeia uma linha do LEFT JOIN para um Job (se existir)
-    fn map_job_from_row(row: VideoWithJobsRow, video: &Arc<Video>) -> Option<Arc<Job>> {
-        let (_, _, _, _, job_id, output_path, status, video_id, error, created_at, updated_at) =
-            row;
+    /// Agrupa as linhas do LEFT JOIN por vídeo, mantendo a ordem da query.
+    /// As linhas de um mesmo vídeo são consecutivas, já que a query ordena
+    /// primeiro pelo vídeo
+    fn map_videos_from_rows(
+        rows: Vec<VideoWithJobsRow>,
+    ) -> Result<Vec<Video>, VideoRepositoryError> {
+        let mut videos: Vec<(Arc<Video>, Vec<Arc<Job>>)> = Vec::new();
+
+        for row in rows {
+            if videos.last().is_none_or(|(video, _)| video.id != row.id) {
+                let video = Arc::new(Video {
+                    id: row.id,
+                    resource_id: row.resource_id.clone(),
+                    file_path: row.file_path.clone(),
+                    created_at: row.created_at,
+                    jobs: Vec::new(),
+                });
+                videos.push((video, Vec::new()));
+            }
+
+            // Linhas sem job associado são ignoradas
+            if let Some((video, jobs)) = videos.last_mut()
+                && let Some(job) = Self::map_job_from_row(row, video)
+            {
+                jobs.push(job?);
+            }
+        }
+
+        Ok(videos
+            .into_iter()
+            .map(|(video, jobs)| Video {
+                jobs,
+                ..video.as_ref().clone()
+            })
+            .collect())
+    }
 
+    fn map_job_from_row(
+        row: VideoWithJobsRow,
+        video: &Arc<Video>,
+    ) -> Option<Result<Arc<Job>, VideoRepositoryError>> {
         // Se job_id é None, significa que não há job nesta linha (LEFT JOIN sem match)
-        let job_id = job_id?;
-        let output_path = output_path?;
-        let status = status?;
-        let video_id = video_id?;
-        let created_at = created_at?;
-        let updated_at = updated_at?;
-
-        Some(Arc::new(Job {
+        let job_id = row.job_id?;
+        let output_path = row.job_output_bucket_path?;
+        let status = row.job_status?;
+        let version = row.job_version?;
+        let attempts = row.job_attempts?;
+        let max_attempts = row.job_max_attempts?;
+        let attempt_errors = row.job_attempt_errors?;
+        let checkpoints = row.job_checkpoints?;
+        let created_at = row.job_created_at?;
+        let updated_at = row.job_updated_at?;
+
+        let status = match status.parse::<JobStatus>() {
+            Ok(status) => status,
+            Err(e) => return Some(Err(VideoRepositoryError::Decode(e.to_string()))),
+        };
+        let attempt_errors = match serde_json::from_str(&attempt_errors) {
+            Ok(attempt_errors) => attempt_errors,
+            Err(e) => {
+                return Some(Err(VideoRepositoryError::Decode(format!(
+                    "attempt_errors: {}",
+                    e
+                ))));
+            }
+        };
+        let checkpoints = match serde_json::from_str(&checkpoints) {
+            Ok(checkpoints) => checkpoints,
+            Err(e) => {
+                return Some(Err(VideoRepositoryError::Decode(format!(
+                    "checkpoints: {}",
+                    e
+                ))));
+            }
+        };
+
+        Some(Ok(Arc::new(Job {
             id: job_id,
             output_bucket_path: output_path,
             status,
             video: Arc::clone(video),
-            video_id,
-            error,
+            video_id: video.id,
+            error: row.job_error,
+            worker_id: row.job_worker_id,
+            lease_expires_at: row.job_lease_expires_at,
+            version,
+            idempotency_key: row.job_idempotency_key,
+            attempts,
+            max_attempts,
+            next_attempt_at: row.job_next_attempt_at,
+            attempt_errors,
+            checkpoints,
+            source_message: row.job_source_message,
+            events: Vec::new(),
             created_at,
             updated_at,
-        }))
+        })))
     }
 }
 
 // Trait bounds organizados por categoria para melhor legibilidade
-impl<DB> Repository<Video> for VideoRepository<DB>
+impl<DB> VideoQueries<DB> for VideoRepository<DB>
 where
     DB: sqlx::Database,
     // Suporte aos tipos usados nas queries
@@ -86,6 +244,10 @@ where
     for<'q> String: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
     for<'q> chrono::DateTime<chrono::Utc>:
         sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    for<'q> Option<String>: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    for<'q> Option<Uuid>: sqlx::Encode<'q, DB> + sqlx::Type<DB>,
+    for<'q> i64: sqlx::Encode<'q, DB> + sqlx::Type<DB> + sqlx::Decode<'q, DB>,
+    for<'q> i32: sqlx::Type<DB> + sqlx::Decode<'q, DB>,
     // Suporte a referências nas queries
     for<'q> &'q Uuid: sqlx::Encode<'q, DB>,
     for<'q> &'q String: sqlx::Encode<'q, DB>,
@@ -93,61 +255,165 @@ where
     // Suporte aos argumentos e executor do sqlx
     for<'q> <DB as sqlx::Database>::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
     for<'c> &'c mut <DB as sqlx::Database>::Connection: sqlx::Executor<'c, Database = DB>,
-    // Suporte a indexação de colunas
+    // Suporte a indexação de colunas por posição e por nome
     usize: sqlx::ColumnIndex<DB::Row>,
+    for<'r> &'r str: sqlx::ColumnIndex<DB::Row>,
 {
-    type Error = VideoRepositoryError;
+    /// Insere um novo vídeo no banco de dados, rejeitando entidades inválidas
+    async fn insert_on(
+        conn: &mut DB::Connection,
+        item: &Video,
+    ) -> Result<Video, VideoRepositoryError> {
+        item.validate()?;
 
-    /// Insere um novo vídeo no banco de dados
-    async fn insert(&self, item: &Video) -> Result<Video, Self::Error> {
         sqlx::query(INSERT_VIDEO_QUERY)
             .bind(item.id)
             .bind(&item.resource_id)
             .bind(&item.file_path)
             .bind(item.created_at)
-            .execute(&self.db.conn)
-            .await
-            .map_err(|e| VideoRepositoryError(e.to_string()))?;
+            .execute(&mut *conn)
+            .await?;
 
         Ok(item.clone())
     }
 
-    /// Busca um vídeo por ID, incluindo todos os jobs associados via LEFT JOIN
-    async fn find(&self, id: &Uuid) -> Result<Video, Self::Error> {
-        // Busca vídeo com jobs em uma única query usando LEFT JOIN
-        let rows = sqlx::query_as::<_, VideoWithJobsRow>(FIND_VIDEO_WITH_JOBS_QUERY)
-            .bind(id)
-            .fetch_all(&self.db.conn)
-            .await
-            .map_err(|e| VideoRepositoryError(e.to_string()))?;
+    /// Atualiza o resource_id e o file_path de um vídeo existente
+    async fn update_on(
+        conn: &mut DB::Connection,
+        item: &Video,
+    ) -> Result<Video, VideoRepositoryError> {
+        item.validate()?;
 
-        if rows.is_empty() {
-            return Err(VideoRepositoryError("Video not found".to_string()));
-        }
+        sqlx::query_as::<_, (Uuid,)>(UPDATE_VIDEO_QUERY)
+            .bind(&item.resource_id)
+            .bind(&item.file_path)
+            .bind(item.id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| VideoRepositoryError::not_found("video", item.id))?;
 
-        // Extrai dados do vídeo da primeira linha
-        let video_data = &rows[0];
-        let video_arc = Arc::new(Video {
-            id: video_data.0,
-            resource_id: video_data.1.clone(),
-            file_path: video_data.2.clone(),
-            created_at: video_data.3,
-            jobs: Vec::new(),
-        });
-
-        // Mapeia jobs encontrados (filtra linhas sem job associado)
-        let jobs = rows
+        Ok(item.clone())
+    }
+
+    /// Lista vídeos sem carregar os jobs (use `find` para obtê-los)
+    async fn list_on(
+        conn: &mut DB::Connection,
+        filter: &VideoFilter,
+        pagination: Pagination,
+    ) -> Result<Page<Video>, VideoRepositoryError> {
+        let rows = sqlx::query_as::<_, VideoRow>(LIST_VIDEOS_QUERY)
+            .bind(filter.resource_id.clone())
+            .bind(i64::from(pagination.limit) + 1)
+            .bind(i64::from(pagination.offset))
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let videos = rows
             .into_iter()
-            .filter_map(|row| Self::map_job_from_row(row, &video_arc))
+            .map(|(id, resource_id, file_path, created_at)| Video {
+                id,
+                resource_id,
+                file_path,
+                created_at,
+                jobs: Vec::new(),
+            })
             .collect();
 
-        Ok(Video {
-            id: video_arc.id,
-            resource_id: video_arc.resource_id.clone(),
-            file_path: video_arc.file_path.clone(),
-            created_at: video_arc.created_at,
-            jobs,
-        })
+        Ok(Page::from_overfetch(videos, pagination))
+    }
+
+    /// Busca um vídeo por ID, incluindo todos os jobs associados (do mais
+    /// recente para o mais antigo) via LEFT JOIN
+    async fn find_on(conn: &mut DB::Connection, id: &Uuid) -> Result<Video, VideoRepositoryError> {
+        let rows = sqlx::query_as::<_, VideoWithJobsRow>(FIND_VIDEOS_WITH_JOBS_QUERY)
+            .bind(Some(*id))
+            .bind(None::<String>)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Self::map_videos_from_rows(rows)?
+            .pop()
+            .ok_or_else(|| VideoRepositoryError::not_found("video", id))
+    }
+
+    /// Busca os vídeos do resource_id com os seus jobs, do mais recente para
+    /// o mais antigo, em uma única query
+    async fn find_by_resource_id_on(
+        conn: &mut DB::Connection,
+        resource_id: &str,
+    ) -> Result<Vec<Video>, VideoRepositoryError> {
+        let rows = sqlx::query_as::<_, VideoWithJobsRow>(FIND_VIDEOS_WITH_JOBS_QUERY)
+            .bind(None::<Uuid>)
+            .bind(Some(resource_id.to_string()))
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Self::map_videos_from_rows(rows)
+    }
+
+    /// Remove o video, retornando NotFound se ele não existir
+    async fn delete_on(conn: &mut DB::Connection, id: &Uuid) -> Result<(), VideoRepositoryError> {
+        sqlx::query_as::<_, (Uuid,)>(DELETE_VIDEO_QUERY)
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| VideoRepositoryError::not_found("video", id))?;
+
+        Ok(())
+    }
+}
+
+impl<DB> Repository<Video> for VideoRepository<DB>
+where
+    DB: sqlx::Database,
+    Self: VideoQueries<DB>,
+{
+    type Error = VideoRepositoryError;
+    type Filter = VideoFilter;
+
+    async fn insert(&self, item: &Video) -> Result<Video, Self::Error> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::insert_on(&mut conn, item).await
+    }
+
+    async fn find(&self, id: &Uuid) -> Result<Video, Self::Error> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::find_on(&mut conn, id).await
+    }
+
+    async fn update(&self, item: &Video) -> Result<Video, Self::Error> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::update_on(&mut conn, item).await
+    }
+
+    async fn list(
+        &self,
+        filter: &VideoFilter,
+        pagination: Pagination,
+    ) -> Result<Page<Video>, Self::Error> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::list_on(&mut conn, filter, pagination).await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), Self::Error> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::delete_on(&mut conn, id).await
+    }
+}
+
+impl<DB> VideoRepository<DB>
+where
+    DB: sqlx::Database,
+    Self: VideoQueries<DB>,
+{
+    /// Retorna os vídeos do resource_id com os seus jobs, dos mais recentes
+    /// para os mais antigos. A lista é vazia se o recurso ainda não tiver vídeo
+    pub async fn find_by_resource_id(
+        &self,
+        resource_id: &str,
+    ) -> Result<Vec<Video>, VideoRepositoryError> {
+        let mut conn = self.db.conn.acquire().await?;
+        Self::find_by_resource_id_on(&mut conn, resource_id).await
     }
 }
 
@@ -157,8 +423,8 @@ mod tests {
     use std::{env, sync::Arc};
 
     use crate::{
-        application::Repository,
-        domain::{Job, Video},
+        application::{JobRepository, Pagination, Repository, VideoFilter},
+        domain::{Job, JobStatus, Video},
         framework::Database,
     };
 
@@ -196,6 +462,48 @@ mod tests {
         assert_eq!(found_video.jobs.len(), 0); // Sem jobs associados
     }
 
+    #[tokio::test]
+    async fn test_video_repository_update() {
+        let db = setup_test_db().await;
+        let video_repo = super::VideoRepository { db };
+
+        let mut video = Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string());
+        video_repo
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+
+        video.file_path = "/path/to/other.mp4".to_string();
+        video_repo
+            .update(&video)
+            .await
+            .expect("Failed to update video");
+
+        let found_video = video_repo
+            .find(&video.id)
+            .await
+            .expect("Failed to find video");
+        assert_eq!(found_video.file_path, "/path/to/other.mp4");
+
+        let missing = Video::new("resource_456".to_string(), "/path/to/video.mp4".to_string());
+        let result = video_repo.update(&missing).await;
+        assert!(result.is_err_and(|e| e.is_not_found()));
+    }
+
+    #[tokio::test]
+    async fn test_video_repository_insert_rejects_invalid_video() {
+        let db = setup_test_db().await;
+        let video_repo = super::VideoRepository { db };
+
+        let invalid_video = Video::new("".to_string(), "/path/to/video.mp4".to_string());
+
+        let result = video_repo.insert(&invalid_video).await;
+        assert!(result.is_err());
+
+        // Nada deve ter sido persistido
+        assert!(video_repo.find(&invalid_video.id).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_video_repository_find_with_jobs() {
         let db = setup_test_db().await;
@@ -219,17 +527,10 @@ mod tests {
         let job_repo = super::super::JobRepository { db };
         let video_arc = Arc::new(new_video.clone());
 
-        let job1 = Job::new(
-            "/output/path1".to_string(),
-            "pending".to_string(),
-            Arc::clone(&video_arc),
-        );
+        let job1 = Job::new("/output/path1".to_string(), Arc::clone(&video_arc));
 
-        let job2 = Job::new(
-            "/output/path2".to_string(),
-            "completed".to_string(),
-            Arc::clone(&video_arc),
-        );
+        let mut job2 = Job::new("/output/path2".to_string(), Arc::clone(&video_arc));
+        job2.fail("encode failed").expect("Failed to fail job2");
 
         job_repo.insert(&job1).await.expect("Failed to insert job1");
 
@@ -249,10 +550,140 @@ mod tests {
         assert!(job_ids.contains(&job1.id));
         assert!(job_ids.contains(&job2.id));
 
+        // Verificar que o status tipado foi lido corretamente
+        let found_job2 = found_video.jobs.iter().find(|j| j.id == job2.id).unwrap();
+        assert_eq!(found_job2.status, JobStatus::Failed);
+
         // Verificar que os jobs têm referência ao vídeo correto
         for job in &found_video.jobs {
             assert_eq!(job.video.id, new_video.id);
             assert_eq!(job.video_id, new_video.id);
         }
     }
+
+    #[tokio::test]
+    async fn test_video_repository_list_by_resource_id() {
+        let db = setup_test_db().await;
+        let video_repo = super::VideoRepository { db };
+
+        for file in ["a.mp4", "b.mp4", "c.mp4"] {
+            let video = Video::new("resource_123".to_string(), file.to_string());
+            video_repo
+                .insert(&video)
+                .await
+                .expect("Failed to insert video");
+        }
+        let other = Video::new("resource_456".to_string(), "d.mp4".to_string());
+        video_repo
+            .insert(&other)
+            .await
+            .expect("Failed to insert video");
+
+        let filter = VideoFilter {
+            resource_id: Some("resource_123".to_string()),
+        };
+
+        let first = video_repo
+            .list(&filter, Pagination::new(2, 0))
+            .await
+            .expect("Failed to list videos");
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(first.next, Some(Pagination::new(2, 2)));
+
+        let second = video_repo
+            .list(&filter, first.next.unwrap())
+            .await
+            .expect("Failed to list videos");
+        assert_eq!(second.items.len(), 1);
+        assert!(second.next.is_none());
+        assert!(
+            first
+                .items
+                .iter()
+                .chain(&second.items)
+                .all(|v| v.resource_id == "resource_123")
+        );
+
+        let all = video_repo
+            .list(&VideoFilter::default(), Pagination::default())
+            .await
+            .expect("Failed to list videos");
+        assert_eq!(all.items.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_video_repository_delete_cascades_to_jobs() {
+        let db = setup_test_db().await;
+        let video_repo = super::VideoRepository { db: db.clone() };
+        let job_repo = JobRepository::new(db);
+
+        let video = Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string());
+        video_repo
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+
+        let job = Job::new("/output/path".to_string(), Arc::new(video.clone()));
+        job_repo.insert(&job).await.expect("Failed to insert job");
+
+        video_repo
+            .delete(&video.id)
+            .await
+            .expect("Failed to delete video");
+
+        let result = job_repo.find(&job.id).await;
+        assert!(result.is_err_and(|e| e.is_not_found()));
+
+        let result = video_repo.delete(&video.id).await;
+        assert!(result.is_err_and(|e| e.is_not_found()));
+    }
+
+    #[tokio::test]
+    async fn test_video_repository_find_by_resource_id_latest_first() {
+        let db = setup_test_db().await;
+        let video_repo = super::VideoRepository { db: db.clone() };
+        let job_repo = JobRepository::new(db);
+
+        let missing = video_repo
+            .find_by_resource_id("resource_123")
+            .await
+            .expect("Failed to find video");
+        assert!(missing.is_empty());
+
+        let mut older = Video::new("resource_123".to_string(), "old.mp4".to_string());
+        older.created_at -= chrono::Duration::minutes(1);
+        let latest = Video::new("resource_123".to_string(), "new.mp4".to_string());
+        let other = Video::new("resource_456".to_string(), "other.mp4".to_string());
+        for video in [&older, &latest, &other] {
+            video_repo
+                .insert(video)
+                .await
+                .expect("Failed to insert video");
+        }
+
+        let mut first = Job::new("/output/path".to_string(), Arc::new(latest.clone()));
+        first.created_at -= chrono::Duration::minutes(1);
+        let second = Job::new("/output/path".to_string(), Arc::new(latest.clone()));
+        for job in [&first, &second] {
+            job_repo.insert(job).await.expect("Failed to insert job");
+        }
+
+        let found = video_repo
+            .find_by_resource_id("resource_123")
+            .await
+            .expect("Failed to find video");
+        let ids: Vec<_> = found.iter().map(|video| video.id).collect();
+        assert_eq!(ids, vec![latest.id, older.id]);
+        let job_ids: Vec<_> = found[0].jobs.iter().map(|job| job.id).collect();
+        assert_eq!(job_ids, vec![second.id, first.id]);
+        assert!(found[1].jobs.is_empty());
+
+        // `find` usa a mesma ordem dos jobs
+        let found = video_repo
+            .find(&latest.id)
+            .await
+            .expect("Failed to find video");
+        let job_ids: Vec<_> = found.jobs.iter().map(|job| job.id).collect();
+        assert_eq!(job_ids, vec![second.id, first.id]);
+    }
 }
@@ -0,0 +1,104 @@
+use std::process::Output;
+
+/// Quantidade de linhas finais do stderr preservadas no erro
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Falha ao executar uma ferramenta do Bento4 (mp4fragment, mp4dash)
+#[derive(Debug)]
+pub enum EncodingError {
+    /// O processo não pôde ser iniciado (binário ausente, sem permissão, ...)
+    Spawn {
+        tool: String,
+        args: Vec<String>,
+        source: std::io::Error,
+    },
+    /// O processo terminou com status diferente de zero; `exit_code` é
+    /// `None` quando ele foi encerrado por um sinal
+    Exit {
+        tool: String,
+        args: Vec<String>,
+        exit_code: Option<i32>,
+        stderr: String,
+    },
+}
+
+impl EncodingError {
+    /// Retorna o erro correspondente à saída do processo, se ele falhou
+    pub fn from_output(tool: &str, args: &[String], output: &Output) -> Option<Self> {
+        if output.status.success() {
+            return None;
+        }
+
+        Some(EncodingError::Exit {
+            tool: tool.to_string(),
+            args: args.to_vec(),
+            exit_code: output.status.code(),
+            stderr: stderr_tail(&output.stderr),
+        })
+    }
+}
+
+impl std::fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodingError::Spawn { tool, args, source } => {
+                write!(f, "failed to run {} {}: {}", tool, args.join(" "), source)
+            }
+            EncodingError::Exit {
+                tool,
+                args,
+                exit_code,
+                stderr,
+            } => {
+                match exit_code {
+                    Some(code) => write!(f, "{} exited with code {}", tool, code)?,
+                    None => write!(f, "{} was terminated by a signal", tool)?,
+                }
+
+                write!(f, " (args: {})", args.join(" "))?;
+
+                if !stderr.is_empty() {
+                    write!(f, ": {}", stderr)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EncodingError::Spawn { source, .. } => Some(source),
+            EncodingError::Exit { .. } => None,
+        }
+    }
+}
+
+/// Últimas linhas não vazias do stderr, unidas em uma única linha
+fn stderr_tail(stderr: &[u8]) -> String {
+    let stderr = String::from_utf8_lossy(stderr);
+    let lines: Vec<_> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stderr_tail_keeps_last_lines() {
+        let stderr: String = (1..=30).map(|i| format!("line {}\n", i)).collect();
+        let tail = stderr_tail(stderr.as_bytes());
+
+        assert!(tail.starts_with("line 11 | "));
+        assert!(tail.ends_with("line 30"));
+        assert_eq!(stderr_tail(b"\n\n"), "");
+    }
+}
@@ -0,0 +1,778 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::{Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+use crate::{
+    application::{
+        JobClaimer, JobCreator, JobNotifier, JobRepositoryError, JobWorker, LeaseLost, Repository,
+        RepositoryError, Submission, VideoRepositoryError,
+    },
+    config::Config,
+    domain::{Job, JobStatus, Video},
+    framework::{DeadLetter, Delivery, ObjectStore, Publisher, QueueError},
+};
+
+/// Mensagem publicada na fila de vídeos a serem encodados
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodeRequest {
+    pub resource_id: String,
+    pub file_path: String,
+    /// Cria um novo job mesmo que o arquivo já tenha um job não falho
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Mensagem equivalente à que criou o job, para os jobs que terminam depois
+/// que a entrega original já foi confirmada
+impl From<&Job> for EncodeRequest {
+    fn from(job: &Job) -> Self {
+        EncodeRequest {
+            resource_id: job.video.resource_id.clone(),
+            file_path: job.video.file_path.clone(),
+            force: false,
+        }
+    }
+}
+
+/// Consome mensagens da fila, cria o Video e o Job correspondentes e
+/// entrega o job ao JobWorker. A mensagem é confirmada quando o job termina
+/// ou quando uma nova tentativa fica agendada no banco (até `max_attempts`
+/// tentativas, reenfileiradas pelo RetryScheduler e processadas por `poll`).
+/// Depois dessa confirmação o banco é a única fonte de verdade do job: um job
+/// em retrying sobrevive à morte do processo e é retomado pelo RetryScheduler
+/// de qualquer réplica, sem depender de uma nova entrega da mensagem.
+/// O resultado das duas origens é publicado pelo JobNotifier: mensagens
+/// inválidas, vídeos inexistentes no bucket e jobs que falham de vez seguem
+/// para a dead-letter exchange
+pub struct JobConsumer<VR, JR, S, P>
+where
+    VR: Repository<Video, Error = VideoRepositoryError> + Clone,
+    JR: Repository<Job, Error = JobRepositoryError>,
+    S: ObjectStore + Clone,
+    P: Publisher,
+{
+    worker: JobWorker<VR, JR, S>,
+    notifier: JobNotifier<P>,
+    output_bucket_name: String,
+    max_attempts: u32,
+}
+
+impl<VR, JR, S, P> JobConsumer<VR, JR, S, P>
+where
+    VR: Repository<Video, Error = VideoRepositoryError> + Clone,
+    JR: Repository<Job, Error = JobRepositoryError> + JobCreator + JobClaimer,
+    S: ObjectStore + Clone,
+    P: Publisher,
+{
+    pub fn new(
+        worker: JobWorker<VR, JR, S>,
+        notifier: JobNotifier<P>,
+        output_bucket_name: String,
+        max_attempts: u32,
+    ) -> Self {
+        JobConsumer {
+            worker,
+            notifier,
+            output_bucket_name,
+            max_attempts: max_attempts.max(1),
+        }
+    }
+
+    /// Usa o bucket de saída e o limite de tentativas da configuração
+    pub fn from_config(
+        worker: JobWorker<VR, JR, S>,
+        notifier: JobNotifier<P>,
+        config: &Config,
+    ) -> Self {
+        Self::new(
+            worker,
+            notifier,
+            config.storage.output_bucket_name.clone(),
+            config.jobs.max_attempts,
+        )
+    }
+
+    /// Processa as mensagens do stream com até `concurrency` jobs simultâneos.
+    /// Retorna quando o stream termina ou quando a fila deixa de responder.
+    /// Um erro ao confirmar ou publicar o resultado de uma mensagem não para
+    /// as demais: ele é registrado e a mensagem volta para a fila
+    pub async fn run<St, D>(&self, deliveries: St, concurrency: usize) -> Result<(), QueueError>
+    where
+        St: Stream<Item = Result<D, QueueError>>,
+        D: Delivery,
+    {
+        deliveries
+            .try_for_each_concurrent(concurrency.max(1), |delivery| async move {
+                if let Err(e) = self.handle(&delivery).await {
+                    tracing::error!("Failed to settle message, requeueing it: {}", e);
+
+                    if let Err(e) = delivery.reject(true).await {
+                        tracing::warn!("Failed to requeue message: {}", e);
+                    }
+                }
+
+                Ok(())
+            })
+            .await
+    }
+
+    /// Processa uma única mensagem, retornando o job criado (se houver)
+    pub async fn handle<D>(&self, delivery: &D) -> Result<Option<Job>, QueueError>
+    where
+        D: Delivery,
+    {
+        let request: EncodeRequest = match serde_json::from_slice(delivery.body()) {
+            Ok(request) => request,
+            Err(e) => {
+                tracing::error!("Discarding invalid message: {}", e);
+                self.dead_letter(delivery, &e.to_string(), "parse", delivery.attempt())
+                    .await?;
+                return Ok(None);
+            }
+        };
+
+        let video = Video::new(request.resource_id, request.file_path);
+
+        if let Err(e) = video.validate() {
+            tracing::error!("Discarding message with {}", e);
+            self.dead_letter(delivery, &e.to_string(), "validate", delivery.attempt())
+                .await?;
+            return Ok(None);
+        }
+
+        let idempotency_key = video.idempotency_key();
+        let mut job = Job::new(self.output_bucket_name.clone(), Arc::new(video));
+        job.idempotency_key = Some(idempotency_key);
+        job.max_attempts = i32::try_from(self.max_attempts).unwrap_or(i32::MAX);
+        // O JSON já foi lido, então o corpo é UTF-8 válido
+        job.source_message = std::str::from_utf8(delivery.body())
+            .ok()
+            .map(str::to_string);
+
+        let job = match self.worker.job_repository.submit(&job, request.force).await {
+            Ok(Submission::Created(job)) => job,
+            // Mensagem reentregue ou submetida em duplicidade: o job existente segue o seu curso
+            Ok(Submission::Existing(existing)) => {
+                tracing::info!(
+                    "Video {} was already submitted as job {} ({})",
+                    existing.video.resource_id,
+                    existing.id,
+                    existing.status
+                );
+
+                // A entrega anterior pode ter voltado para a fila por uma falha
+                // ao publicar a conclusão, então a notificação é refeita
+                if existing.status == JobStatus::Completed {
+                    self.notifier.notify_success(&existing).await?;
+                }
+
+                delivery.ack().await?;
+                return Ok(Some(existing));
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to store video {} and job {}: {}",
+                    job.video_id,
+                    job.id,
+                    e
+                );
+                self.settle_repository_error(delivery, &e).await?;
+                return Ok(None);
+            }
+        };
+
+        match self.worker.execute(job).await {
+            Ok((job, None)) if job.status == JobStatus::Completed => {
+                self.notifier.notify_success(&job).await?;
+                delivery.ack().await?;
+                Ok(Some(job))
+            }
+            // A próxima tentativa já está agendada no banco, que passa a
+            // conduzir o job até o fim; manter a mensagem sem confirmação
+            // ocuparia o prefetch durante todo o backoff
+            Ok((job, Some(_))) if job.status == JobStatus::Retrying => {
+                delivery.ack().await?;
+                Ok(Some(job))
+            }
+            Ok((job, Some(failure))) => {
+                let error = job.error.as_deref().unwrap_or("job failed");
+                self.dead_letter(delivery, error, failure.stage.as_str(), attempts(&job))
+                    .await?;
+
+                Ok(Some(job))
+            }
+            Ok((job, None)) => {
+                tracing::error!(
+                    "Job {} stopped in non-terminal state {}",
+                    job.id,
+                    job.status
+                );
+                delivery.reject(true).await?;
+                Ok(Some(job))
+            }
+            Err(e) => {
+                tracing::error!("Failed to process job: {:#}", e);
+
+                // Outro worker assumiu o job e vai concluí-lo
+                if e.is::<LeaseLost>() {
+                    delivery.ack().await?;
+                    return Ok(None);
+                }
+
+                match e.chain().find_map(|e| e.downcast_ref::<RepositoryError>()) {
+                    // O job foi removido ou alterado por outro processo enquanto era
+                    // processado: não há o que tentar novamente
+                    Some(error) if error.is_not_found() || error.is_version_conflict() => {
+                        delivery.ack().await?
+                    }
+                    Some(error) => self.settle_repository_error(delivery, error).await?,
+                    None => delivery.reject(true).await?,
+                }
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Erros transitórios devolvem a mensagem à fila; os demais não vão se
+    /// resolver com uma nova entrega e seguem para a dead-letter exchange
+    async fn settle_repository_error<D>(
+        &self,
+        delivery: &D,
+        error: &RepositoryError,
+    ) -> Result<(), QueueError>
+    where
+        D: Delivery,
+    {
+        if error.is_transient() {
+            delivery.reject(true).await
+        } else {
+            self.dead_letter(delivery, &error.to_string(), "persist", delivery.attempt())
+                .await
+        }
+    }
+
+    /// Notifica a falha e envia a mensagem para a dead-letter exchange
+    async fn dead_letter<D>(
+        &self,
+        delivery: &D,
+        reason: &str,
+        stage: &str,
+        attempts: u32,
+    ) -> Result<(), QueueError>
+    where
+        D: Delivery,
+    {
+        self.notifier.notify_error(delivery.body(), reason).await?;
+
+        delivery
+            .dead_letter(&DeadLetter::new(reason, stage, attempts))
+            .await
+    }
+
+    /// Reivindica o próximo job pendente (uma nova tentativa devolvida pelo
+    /// RetryScheduler ou um job recuperado), o processa e publica o resultado.
+    /// Retorna `None` quando não há jobs pendentes
+    pub async fn process_next(&self, worker_id: &str) -> anyhow::Result<Option<Job>> {
+        let Some(job) = self.worker.process_next(worker_id).await? else {
+            return Ok(None);
+        };
+
+        self.finish(&job).await?;
+
+        Ok(Some(job))
+    }
+
+    /// Processa jobs pendentes até que `shutdown` seja sinalizado, esperando
+    /// `poll_interval` quando não há jobs. O job em andamento termina antes de
+    /// o loop retornar; erros são registrados e o loop continua
+    pub async fn poll(
+        &self,
+        worker_id: &str,
+        poll_interval: Duration,
+        mut shutdown: watch::Receiver<bool>,
+    ) {
+        while !*shutdown.borrow() {
+            match self.process_next(worker_id).await {
+                Ok(Some(_)) => continue,
+                Ok(None) => {}
+                Err(e) => tracing::error!("Worker {} failed to process job: {:#}", worker_id, e),
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => {}
+                _ = shutdown.changed() => {}
+            }
+        }
+
+        tracing::info!("Worker {} stopped", worker_id);
+    }
+
+    /// Publica o resultado de um job cuja entrega já foi confirmada: a
+    /// conclusão, ou o erro e a mensagem original na dead-letter exchange
+    async fn finish(&self, job: &Job) -> Result<(), QueueError> {
+        match job.status {
+            JobStatus::Completed => self.notifier.notify_success(job).await,
+            JobStatus::Failed => {
+                // Jobs criados sem mensagem (ex.: antes de a mensagem ser
+                // gravada) recebem uma equivalente, montada a partir do job
+                let body = match &job.source_message {
+                    Some(message) => message.clone().into_bytes(),
+                    None => serde_json::to_vec(&EncodeRequest::from(job))
+                        .map_err(|e| QueueError(e.to_string()))?,
+                };
+                let reason = job.error.as_deref().unwrap_or("job failed");
+                let stage = job
+                    .attempt_errors
+                    .last()
+                    .map_or("unknown", |e| e.stage.as_str());
+
+                self.notifier.notify_error(&body, reason).await?;
+                self.notifier
+                    .dead_letter(&body, &DeadLetter::new(reason, stage, attempts(job)))
+                    .await
+            }
+            // Com tentativas restantes o RetryScheduler devolve o job para a fila
+            _ => Ok(()),
+        }
+    }
+}
+
+fn attempts(job: &Job) -> u32 {
+    u32::try_from(job.attempts).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::Sqlite;
+    use std::{env, path::PathBuf};
+
+    use super::*;
+    use crate::{
+        application::{
+            InMemoryDatabase, InMemoryJobRepository, InMemoryVideoRepository, JobRepository,
+            RetryScheduler, VideoRepository,
+        },
+        framework::{
+            Database, LocalObjectStore,
+            queue::{DeliveryOutcome, InMemoryBroker},
+        },
+    };
+
+    fn setup_consumer(
+        broker: &InMemoryBroker,
+        store_root: PathBuf,
+        max_attempts: u32,
+    ) -> JobConsumer<InMemoryVideoRepository, InMemoryJobRepository, LocalObjectStore, InMemoryBroker>
+    {
+        let db = InMemoryDatabase::new();
+        let worker = JobWorker::new(
+            db.videos(),
+            db.jobs(),
+            LocalObjectStore::new(store_root),
+            "input".to_string(),
+        );
+
+        let notifier =
+            JobNotifier::new(broker.clone(), "amq.direct".to_string(), "jobs".to_string());
+
+        JobConsumer::new(worker, notifier, "output".to_string(), max_attempts)
+    }
+
+    fn store_root() -> PathBuf {
+        env::temp_dir().join(format!("store-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_dead_letters_missing_source() {
+        let broker = InMemoryBroker::new();
+        let consumer = setup_consumer(&broker, store_root(), 3);
+
+        let delivery =
+            broker.send(r#"{"resource_id":"resource_123","file_path":"videos/missing.mp4"}"#);
+
+        let job = consumer
+            .handle(&delivery)
+            .await
+            .expect("Failed to handle delivery")
+            .expect("Job should have been created");
+
+        // O arquivo não existe no bucket: o job termina como failed e a
+        // mensagem vai direto para a DLX, sem gastar as demais tentativas
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.output_bucket_path, "output");
+        assert_eq!(
+            delivery.outcome(),
+            DeliveryOutcome::DeadLettered(DeadLetter::new(
+                job.error.clone().unwrap(),
+                "downloading",
+                1
+            ))
+        );
+
+        let video = consumer
+            .worker
+            .video_repository
+            .find(&job.video_id)
+            .await
+            .expect("Failed to find video");
+        assert_eq!(video.resource_id, "resource_123");
+        assert_eq!(video.jobs.len(), 1);
+        assert_eq!(video.jobs[0].status, JobStatus::Failed);
+
+        // A falha é notificada com a mensagem original
+        let published = broker.published();
+        assert_eq!(published.len(), 1);
+        let notification: serde_json::Value = serde_json::from_slice(&published[0].body).unwrap();
+        assert_eq!(
+            notification["message"],
+            r#"{"resource_id":"resource_123","file_path":"videos/missing.mp4"}"#
+        );
+        assert_eq!(notification["error"], job.error.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_retries_until_dead_letter() {
+        // A raiz do store é um arquivo, então toda leitura falha com um erro
+        // de IO que não é "not found" e o job pode ser tentado novamente
+        let root = store_root();
+        tokio::fs::write(&root, b"").await.unwrap();
+
+        let broker = InMemoryBroker::new();
+        let consumer = setup_consumer(&broker, root.clone(), 2);
+
+        // Campos desconhecidos também precisam chegar à DLX
+        let message = r#"{"resource_id":"resource_123","file_path":"videos/source.mp4","force":true,"trace_id":"abc"}"#;
+        let delivery = broker.send(message);
+
+        let job = consumer
+            .handle(&delivery)
+            .await
+            .expect("Failed to handle delivery")
+            .expect("Job should have been created");
+
+        // A mensagem é confirmada e a nova tentativa fica agendada no banco
+        assert_eq!(job.status, JobStatus::Retrying);
+        assert_eq!((job.attempts, job.max_attempts), (1, 2));
+        assert_eq!(delivery.outcome(), DeliveryOutcome::Acked);
+        assert!(broker.published().is_empty());
+
+        let scheduler = RetryScheduler::new(consumer.worker.job_repository.clone());
+        let requeued = scheduler
+            .requeue_due(chrono::Utc::now() + chrono::Duration::days(1))
+            .await
+            .expect("Failed to requeue jobs");
+        assert_eq!(requeued.len(), 1);
+
+        let job = consumer
+            .process_next("worker-1")
+            .await
+            .expect("Failed to process job")
+            .expect("Requeued job should be claimed");
+
+        // Sem tentativas restantes o job falha de vez, com o erro de cada tentativa
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.attempts, 2);
+        assert_eq!(job.attempt_errors.len(), 2);
+        assert!(
+            job.attempt_errors
+                .iter()
+                .all(|e| e.error.starts_with("failed to download source video"))
+        );
+
+        // A falha é notificada e a mensagem do job segue para a DLX
+        let error = job.error.clone().unwrap();
+        let published = broker.published();
+        assert_eq!(published.len(), 1);
+        let notification: serde_json::Value = serde_json::from_slice(&published[0].body).unwrap();
+        assert_eq!(notification["error"], error);
+
+        let dead_lettered = broker.dead_lettered();
+        assert_eq!(dead_lettered.len(), 1);
+        assert_eq!(
+            dead_lettered[0].letter,
+            DeadLetter::new(error, "downloading", 2)
+        );
+        // A mensagem original segue sem alterações
+        assert_eq!(dead_lettered[0].body, message.as_bytes());
+
+        tokio::fs::remove_file(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_retry_survives_process_restart() {
+        let root = store_root();
+        tokio::fs::write(&root, b"").await.unwrap();
+
+        let broker = InMemoryBroker::new();
+        let consumer = setup_consumer(&broker, root.clone(), 2);
+        let (videos, jobs) = (
+            consumer.worker.video_repository.clone(),
+            consumer.worker.job_repository.clone(),
+        );
+
+        let delivery =
+            broker.send(r#"{"resource_id":"resource_123","file_path":"videos/source.mp4"}"#);
+        let job = consumer
+            .handle(&delivery)
+            .await
+            .expect("Failed to handle delivery")
+            .expect("Job should have been created");
+        assert_eq!(job.status, JobStatus::Retrying);
+        assert_eq!(delivery.outcome(), DeliveryOutcome::Acked);
+
+        // O processo morre antes de o RetryScheduler reenfileirar o job; não
+        // há mais mensagem na fila, só o job gravado no banco
+        drop(consumer);
+
+        let restarted = JobConsumer::new(
+            JobWorker::new(
+                videos,
+                jobs.clone(),
+                LocalObjectStore::new(&root),
+                "input".to_string(),
+            ),
+            JobNotifier::new(broker.clone(), "amq.direct".to_string(), "jobs".to_string()),
+            "output".to_string(),
+            2,
+        );
+        RetryScheduler::new(jobs.clone())
+            .requeue_due(chrono::Utc::now() + chrono::Duration::days(1))
+            .await
+            .expect("Failed to requeue jobs");
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let stop = async {
+            while !jobs.find(&job.id).await.unwrap().status.is_terminal() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            shutdown_tx.send_replace(true);
+        };
+
+        let poll = restarted.poll("worker-2", Duration::from_secs(60), shutdown_rx);
+        tokio::time::timeout(Duration::from_secs(5), async { tokio::join!(poll, stop) })
+            .await
+            .expect("Worker should stop after shutdown");
+
+        // O job chega ao estado terminal e o resultado é publicado
+        assert_eq!(jobs.find(&job.id).await.unwrap().status, JobStatus::Failed);
+        assert_eq!(broker.published().len(), 1);
+        assert_eq!(broker.dead_lettered()[0].letter.attempts, 2);
+
+        tokio::fs::remove_file(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_poll_processes_jobs_until_shutdown() {
+        let broker = InMemoryBroker::new();
+        let consumer = setup_consumer(&broker, store_root(), 3);
+        let jobs = &consumer.worker.job_repository;
+
+        // Um job devolvido para pending, sem entrega na fila
+        let video = Video::new("resource_123".to_string(), "videos/missing.mp4".to_string());
+        let job = Job::new("output".to_string(), Arc::new(video));
+        jobs.create_with_video(&job)
+            .await
+            .expect("Failed to create job");
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        // Pede o desligamento assim que o job é processado; o intervalo longo
+        // garante que o worker acorda pelo sinal, e não pelo timer
+        let stop = async {
+            while jobs.find(&job.id).await.unwrap().status != JobStatus::Failed {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            shutdown_tx.send_replace(true);
+        };
+
+        let poll = consumer.poll("worker-1", Duration::from_secs(60), shutdown_rx);
+        tokio::time::timeout(Duration::from_secs(5), async { tokio::join!(poll, stop) })
+            .await
+            .expect("Worker should stop after shutdown");
+
+        let found_job = jobs.find(&job.id).await.unwrap();
+        assert_eq!(found_job.worker_id.as_deref(), Some("worker-1"));
+
+        // O vídeo não existe: a falha definitiva também chega à DLX
+        assert_eq!(broker.published().len(), 1);
+        assert_eq!(broker.dead_lettered()[0].letter.stage, "downloading");
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_requeues_on_connection_error() {
+        let database_url =
+            env::var("DATABASE_URL_TEST").unwrap_or_else(|_| "sqlite::memory:".to_string());
+        let db = Database::<Sqlite>::new(database_url, Some(true))
+            .await
+            .expect("Failed to create test database connection");
+
+        let broker = InMemoryBroker::new();
+        let worker = JobWorker::new(
+            VideoRepository::new(db.clone()),
+            JobRepository::new(db.clone()),
+            LocalObjectStore::new(store_root()),
+            "input".to_string(),
+        );
+        let notifier =
+            JobNotifier::new(broker.clone(), "amq.direct".to_string(), "jobs".to_string());
+        let consumer = JobConsumer::new(worker, notifier, "output".to_string(), 3);
+
+        // Com o pool fechado o erro é transitório e a mensagem volta para a fila
+        db.conn.close().await;
+
+        let delivery =
+            broker.send(r#"{"resource_id":"resource_123","file_path":"videos/source.mp4"}"#);
+
+        let job = consumer
+            .handle(&delivery)
+            .await
+            .expect("Failed to handle delivery");
+
+        assert!(job.is_none());
+        assert_eq!(
+            delivery.outcome(),
+            DeliveryOutcome::Rejected { requeue: true }
+        );
+        assert!(broker.published().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_returns_existing_job_for_duplicate_message() {
+        let broker = InMemoryBroker::new();
+        let consumer = setup_consumer(&broker, store_root(), 3);
+        let jobs = &consumer.worker.job_repository;
+
+        // Um job do mesmo arquivo ainda em andamento
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        let mut pending = Job::new("output".to_string(), Arc::new(video.clone()));
+        pending.idempotency_key = Some(video.idempotency_key());
+        jobs.create_with_video(&pending)
+            .await
+            .expect("Failed to create job");
+
+        let duplicate =
+            broker.send(r#"{"resource_id":"resource_123","file_path":"videos/source.mp4"}"#);
+
+        let job = consumer
+            .handle(&duplicate)
+            .await
+            .expect("Failed to handle delivery")
+            .expect("Existing job should be returned");
+
+        assert_eq!(job.id, pending.id);
+        assert_eq!(job.status, JobStatus::Pending);
+        assert_eq!(duplicate.outcome(), DeliveryOutcome::Acked);
+        assert!(broker.published().is_empty());
+
+        // Com force um novo job é criado e processado
+        let forced = broker
+            .send(r#"{"resource_id":"resource_123","file_path":"videos/source.mp4","force":true}"#);
+
+        let job = consumer
+            .handle(&forced)
+            .await
+            .expect("Failed to handle delivery")
+            .expect("Job should have been created");
+
+        assert_ne!(job.id, pending.id);
+        assert_eq!(job.status, JobStatus::Failed);
+
+        let previous = jobs.find(&pending.id).await.expect("Failed to find job");
+        assert_eq!(previous.status, JobStatus::Pending);
+        assert_eq!(previous.idempotency_key, None);
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_renotifies_completed_job_on_redelivery() {
+        let broker = InMemoryBroker::new();
+        let consumer = setup_consumer(&broker, store_root(), 3);
+        let jobs = &consumer.worker.job_repository;
+
+        // O job terminou, mas a notificação da conclusão não foi publicada
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        let mut job = Job::new("output".to_string(), Arc::new(video.clone()));
+        job.idempotency_key = Some(video.idempotency_key());
+        let mut job = jobs
+            .create_with_video(&job)
+            .await
+            .expect("Failed to create job");
+        job.status = JobStatus::Completed;
+        let completed = jobs.update(&job).await.expect("Failed to update job");
+
+        let redelivered =
+            broker.send(r#"{"resource_id":"resource_123","file_path":"videos/source.mp4"}"#);
+
+        let job = consumer
+            .handle(&redelivered)
+            .await
+            .expect("Failed to handle delivery")
+            .expect("Existing job should be returned");
+
+        assert_eq!(job.id, completed.id);
+        assert_eq!(redelivered.outcome(), DeliveryOutcome::Acked);
+
+        let published = broker.published();
+        assert_eq!(published.len(), 1);
+        let notification: serde_json::Value = serde_json::from_slice(&published[0].body).unwrap();
+        assert_eq!(notification["job_id"], completed.id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_run_continues_after_delivery_error() {
+        let broker = InMemoryBroker::new();
+        let consumer = setup_consumer(&broker, store_root(), 3);
+
+        // A primeira mensagem já foi confirmada, então confirmá-la de novo
+        // falha; a segunda ainda precisa ser processada
+        let settled = broker.send("not json");
+        settled.ack().await.unwrap();
+        let next = broker.send("not json either");
+        broker.close();
+
+        consumer
+            .run(broker.consume(), 1)
+            .await
+            .expect("Consumer should drain the queue");
+
+        assert_eq!(settled.outcome(), DeliveryOutcome::Acked);
+        assert!(matches!(
+            next.outcome(),
+            DeliveryOutcome::DeadLettered(DeadLetter { ref stage, .. }) if stage == "parse"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_job_consumer_dead_letters_invalid_messages() {
+        let broker = InMemoryBroker::new();
+        let consumer = setup_consumer(&broker, store_root(), 3);
+
+        let malformed = broker.send("not json");
+        let missing_fields = broker.send(r#"{"resource_id":"","file_path":"video.mp4"}"#);
+        broker.close();
+
+        consumer
+            .run(broker.consume(), 1)
+            .await
+            .expect("Consumer should drain the queue");
+
+        match malformed.outcome() {
+            DeliveryOutcome::DeadLettered(letter) => {
+                assert_eq!(letter.stage, "parse");
+                assert_eq!(letter.attempts, 1);
+            }
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        }
+        assert_eq!(
+            missing_fields.outcome(),
+            DeliveryOutcome::DeadLettered(DeadLetter::new(
+                "invalid video: resource_id must not be empty",
+                "validate",
+                1
+            ))
+        );
+        assert_eq!(broker.published().len(), 2);
+    }
+}
@@ -0,0 +1,119 @@
+use serde::Serialize;
+
+use crate::{
+    domain::Job,
+    framework::{DeadLetter, Publisher, QueueError},
+};
+
+/// Envelope publicado quando um job falha, com a mensagem original recebida
+#[derive(Debug, Clone, Serialize)]
+pub struct JobErrorNotification {
+    pub message: String,
+    pub error: String,
+}
+
+/// Publica o resultado dos jobs na exchange de notificação, para que os
+/// serviços de catálogo saibam quando um vídeo está disponível, e envia para
+/// a dead-letter exchange os jobs que falham de vez sem uma entrega pendente
+pub struct JobNotifier<P>
+where
+    P: Publisher,
+{
+    publisher: P,
+    exchange: String,
+    routing_key: String,
+}
+
+impl<P> JobNotifier<P>
+where
+    P: Publisher,
+{
+    pub fn new(publisher: P, exchange: String, routing_key: String) -> Self {
+        JobNotifier {
+            publisher,
+            exchange,
+            routing_key,
+        }
+    }
+
+    /// Publica o job concluído (job_id, status, video.encoded_video_folder, ...)
+    pub async fn notify_success(&self, job: &Job) -> Result<(), QueueError> {
+        let body = serde_json::to_vec(job).map_err(|e| QueueError(e.to_string()))?;
+
+        self.publisher
+            .publish(&self.exchange, &self.routing_key, &body)
+            .await?;
+
+        tracing::info!("Published completion of job {}", job.id);
+
+        Ok(())
+    }
+
+    /// Publica o envelope de erro contendo a mensagem original
+    pub async fn notify_error(&self, original: &[u8], error: &str) -> Result<(), QueueError> {
+        let notification = JobErrorNotification {
+            message: String::from_utf8_lossy(original).to_string(),
+            error: error.to_string(),
+        };
+        let body = serde_json::to_vec(&notification).map_err(|e| QueueError(e.to_string()))?;
+
+        self.publisher
+            .publish(&self.exchange, &self.routing_key, &body)
+            .await
+    }
+
+    /// Publica a mensagem do job na dead-letter exchange com os headers de falha
+    pub async fn dead_letter(
+        &self,
+        original: &[u8],
+        letter: &DeadLetter,
+    ) -> Result<(), QueueError> {
+        self.publisher.publish_dead_letter(original, letter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{domain::Video, framework::queue::InMemoryBroker};
+
+    #[tokio::test]
+    async fn test_job_notifier_publishes_job_and_error_envelope() {
+        let broker = InMemoryBroker::new();
+        let notifier =
+            JobNotifier::new(broker.clone(), "amq.direct".to_string(), "jobs".to_string());
+
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        let job = Job::new("output".to_string(), Arc::new(video));
+
+        notifier
+            .notify_success(&job)
+            .await
+            .expect("Failed to notify success");
+        notifier
+            .notify_error(b"{\"resource_id\":\"a\"}", "failed to encode video")
+            .await
+            .expect("Failed to notify error");
+
+        let published = broker.published();
+        assert_eq!(published.len(), 2);
+        assert!(
+            published
+                .iter()
+                .all(|m| m.exchange == "amq.direct" && m.routing_key == "jobs")
+        );
+
+        let success: serde_json::Value = serde_json::from_slice(&published[0].body).unwrap();
+        assert_eq!(success["job_id"], job.id.to_string());
+        assert_eq!(
+            success["video"]["encoded_video_folder"],
+            job.video_id.to_string()
+        );
+
+        let error: serde_json::Value = serde_json::from_slice(&published[1].body).unwrap();
+        assert_eq!(error["message"], "{\"resource_id\":\"a\"}");
+        assert_eq!(error["error"], "failed to encode video");
+    }
+}
@@ -0,0 +1,337 @@
+use std::{env, time::Duration};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    application::{JobFilter, JobRepositoryError, Pagination, Repository, WorkspaceManager},
+    config::Config,
+    domain::{Job, JobStatus},
+};
+
+const DEFAULT_STALE_AFTER: chrono::Duration = chrono::Duration::hours(1);
+const DEFAULT_RECOVERY_INTERVAL: Duration = Duration::from_secs(60);
+
+// Estágios em que um worker está com o job; um job parado em um deles sem
+// um lease válido ficou para trás quando o processo morreu
+const ACTIVE_STATUSES: [JobStatus; 5] = [
+    JobStatus::Downloading,
+    JobStatus::Fragmenting,
+    JobStatus::Encoding,
+    JobStatus::Uploading,
+    JobStatus::Finishing,
+];
+
+/// O que a recuperação fez com os jobs encontrados parados
+#[derive(Debug, Default)]
+pub struct RecoveryReport {
+    /// Jobs devolvidos para pending para uma nova tentativa
+    pub requeued: Vec<Uuid>,
+    /// Jobs que já tinham usado todas as tentativas
+    pub failed: Vec<Uuid>,
+    /// Jobs alterados por outro processo durante a recuperação
+    pub skipped: usize,
+}
+
+impl RecoveryReport {
+    pub fn recovered(&self) -> usize {
+        self.requeued.len() + self.failed.len()
+    }
+}
+
+/// Recuperação dos jobs deixados em um estágio ativo por um processo que
+/// morreu, na inicialização e a cada `interval`: um job está abandonado quando
+/// o lease expirou ou, sem lease, quando não muda há `stale_after`. O job é
+/// devolvido para pending ou marcado como falho,
+/// conforme as tentativas restantes, e remove o workspace em ambos os casos.
+/// O job devolvido pode ser reivindicado por outra réplica, que não veria os
+/// arquivos deste disco; os checkpoints sem arquivo são refeitos pela nova
+/// tentativa
+pub struct JobRecovery<JR>
+where
+    JR: Repository<Job, Error = JobRepositoryError, Filter = JobFilter>,
+{
+    pub job_repository: JR,
+    pub workspaces: WorkspaceManager,
+    /// Há quanto tempo um job sem lease precisa estar sem mudar de estágio
+    /// para ser considerado abandonado
+    pub stale_after: chrono::Duration,
+    pub interval: Duration,
+}
+
+impl<JR> JobRecovery<JR>
+where
+    JR: Repository<Job, Error = JobRepositoryError, Filter = JobFilter>,
+{
+    pub fn new(job_repository: JR) -> Self {
+        JobRecovery {
+            job_repository,
+            workspaces: WorkspaceManager::new(env::temp_dir()),
+            stale_after: DEFAULT_STALE_AFTER,
+            interval: DEFAULT_RECOVERY_INTERVAL,
+        }
+    }
+
+    pub fn from_config(job_repository: JR, config: &Config) -> Self {
+        JobRecovery {
+            job_repository,
+            workspaces: WorkspaceManager::from_config(&config.storage),
+            stale_after: chrono::Duration::from_std(config.jobs.stale_after)
+                .unwrap_or(DEFAULT_STALE_AFTER),
+            interval: config.jobs.recovery_interval,
+        }
+    }
+
+    /// Recupera os jobs abandonados até `now` e registra um resumo
+    pub async fn recover(&self, now: DateTime<Utc>) -> Result<RecoveryReport, JobRepositoryError> {
+        let mut report = RecoveryReport::default();
+
+        for job in self.find_stale(now).await? {
+            let job_id = job.id;
+
+            match self.recover_job(job, now).await {
+                Ok(job) if job.status == JobStatus::Failed => report.failed.push(job_id),
+                Ok(_) => report.requeued.push(job_id),
+                Err(e) if e.is_version_conflict() || e.is_not_found() => {
+                    tracing::debug!("Job {} changed before it could be recovered: {}", job_id, e);
+                    report.skipped += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if report.recovered() > 0 {
+            tracing::warn!(
+                "Recovered {} stale jobs: {} requeued ({:?}), {} failed ({:?})",
+                report.recovered(),
+                report.requeued.len(),
+                report.requeued,
+                report.failed.len(),
+                report.failed
+            );
+        } else {
+            tracing::debug!("No stale jobs to recover");
+        }
+
+        Ok(report)
+    }
+
+    /// Executa `recover` a cada `interval`, a partir de um intervalo depois da
+    /// chamada (a recuperação da inicialização é feita antes, por `recover`).
+    /// Erros são registrados e a próxima rodada tenta novamente
+    pub async fn run(&self) {
+        let start = tokio::time::Instant::now() + self.interval;
+        let mut ticker = tokio::time::interval_at(start, self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = self.recover(Utc::now()).await {
+                tracing::error!("Failed to recover stale jobs: {}", e);
+            }
+        }
+    }
+
+    async fn find_stale(&self, now: DateTime<Utc>) -> Result<Vec<Job>, JobRepositoryError> {
+        let mut stale = Vec::new();
+
+        for status in ACTIVE_STATUSES {
+            // Os dois filtros são disjuntos: com o lease expirado, ou sem lease
+            // e sem mudanças há `stale_after`
+            let expired = JobFilter {
+                status: Some(status),
+                lease_expired_before: Some(now),
+                ..Default::default()
+            };
+            let unleased = JobFilter {
+                status: Some(status),
+                updated_before: Some(now - self.stale_after),
+                without_lease: true,
+                ..Default::default()
+            };
+
+            // Lê todas as páginas antes de atualizar, já que os jobs recuperados
+            // deixam de atender o filtro e deslocariam os offsets
+            for filter in [expired, unleased] {
+                let mut pagination = Some(Pagination::default());
+                while let Some(current) = pagination {
+                    let page = self.job_repository.list(&filter, current).await?;
+                    stale.extend(page.items);
+                    pagination = page.next;
+                }
+            }
+        }
+
+        Ok(stale)
+    }
+
+    /// Registra a tentativa interrompida e, só depois de gravar o job, remove
+    /// o workspace: se outro processo alterou o job, os arquivos são dele
+    async fn recover_job(
+        &self,
+        mut job: Job,
+        now: DateTime<Utc>,
+    ) -> Result<Job, JobRepositoryError> {
+        let message = match &job.worker_id {
+            Some(worker_id) => format!(
+                "attempt interrupted while {}: worker {} stopped responding",
+                job.status, worker_id
+            ),
+            None => format!("attempt interrupted while {}", job.status),
+        };
+
+        // Com tentativas restantes o job pode voltar para a fila imediatamente
+        job.fail_attempt(message, Some(now))?;
+        if job.status == JobStatus::Retrying {
+            job.requeue()?;
+        }
+
+        let job = self.job_repository.update(&job).await?;
+        if let Err(e) = self.workspaces.remove(job.id).await {
+            tracing::warn!("Failed to remove workspace of job {}: {}", job.id, e);
+        }
+
+        Ok(job)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        application::{InMemoryDatabase, JobClaimer},
+        domain::Video,
+    };
+
+    #[tokio::test]
+    async fn test_job_recovery_requeues_or_fails_stale_jobs() {
+        let db = InMemoryDatabase::new();
+        let jobs = db.jobs();
+        let now = Utc::now();
+        let lease = chrono::Duration::minutes(5);
+
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        db.videos().insert(&video).await.unwrap();
+
+        let mut claimed = Vec::new();
+        for max_attempts in [3, 1, 3] {
+            let mut job = Job::new("output".to_string(), Arc::new(video.clone()));
+            job.max_attempts = max_attempts;
+            jobs.insert(&job).await.unwrap();
+
+            let mut job = jobs.claim_next("worker-1", lease).await.unwrap().unwrap();
+            job.transition_to(JobStatus::Fragmenting).unwrap();
+            claimed.push(jobs.update(&job).await.unwrap());
+        }
+
+        // Os dois primeiros jobs ficaram para trás há duas horas; o terceiro
+        // segue com um worker ativo
+        let root = env::temp_dir().join(format!("recovery-{}", Uuid::new_v4()));
+        let workspaces = WorkspaceManager::new(&root);
+        for job in &claimed {
+            let mut workspace = workspaces.workspace(job.id, video.id);
+            workspace.prepare().await.unwrap();
+            tokio::fs::write(workspace.source_path(), b"mp4")
+                .await
+                .unwrap();
+            workspace.keep();
+        }
+
+        let recovery = JobRecovery {
+            workspaces,
+            ..JobRecovery::new(jobs.clone())
+        };
+        let later = now + chrono::Duration::hours(2);
+        let mut active = claimed[2].clone();
+        active.transition_to(JobStatus::Encoding).unwrap();
+        active.updated_at = later;
+        active.lease_expires_at = Some(later + lease);
+        jobs.update(&active).await.unwrap();
+
+        let report = recovery.recover(later).await.expect("Failed to recover");
+
+        assert_eq!(report.requeued, vec![claimed[0].id]);
+        assert_eq!(report.failed, vec![claimed[1].id]);
+        assert_eq!(report.skipped, 0);
+
+        let requeued = jobs.find(&claimed[0].id).await.unwrap();
+        assert_eq!(requeued.status, JobStatus::Pending);
+        assert_eq!(requeued.worker_id, None);
+        assert_eq!(requeued.attempt_errors[0].stage, JobStatus::Fragmenting);
+        assert!(
+            requeued.attempt_errors[0]
+                .error
+                .contains("worker worker-1 stopped responding")
+        );
+
+        let failed = jobs.find(&claimed[1].id).await.unwrap();
+        assert_eq!(failed.status, JobStatus::Failed);
+
+        let untouched = jobs.find(&claimed[2].id).await.unwrap();
+        assert_eq!(untouched.status, JobStatus::Encoding);
+
+        // Os arquivos dos jobs recuperados são removidos; os do job ativo não
+        assert!(!root.join(claimed[0].id.to_string()).exists());
+        assert!(!root.join(claimed[1].id.to_string()).exists());
+        assert!(root.join(claimed[2].id.to_string()).exists());
+
+        // Uma segunda execução não encontra mais nada
+        let report = recovery.recover(later).await.expect("Failed to recover");
+        assert_eq!(report.recovered(), 0);
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_job_recovery_recovers_expired_leases_before_stale_after() {
+        let db = InMemoryDatabase::new();
+        let jobs = db.jobs();
+        let now = Utc::now();
+        let lease = chrono::Duration::minutes(5);
+
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        db.videos().insert(&video).await.unwrap();
+
+        let mut claimed = Vec::new();
+        for _ in 0..2 {
+            let job = Job::new("output".to_string(), Arc::new(video.clone()));
+            jobs.insert(&job).await.unwrap();
+
+            let mut job = jobs.claim_next("worker-1", lease).await.unwrap().unwrap();
+            job.transition_to(JobStatus::Fragmenting).unwrap();
+            claimed.push(jobs.update(&job).await.unwrap());
+        }
+
+        // Um job ativo sem lease (ex.: gravado antes da existência dos leases)
+        // só é considerado abandonado depois de `stale_after`
+        let mut unleased = claimed[1].clone();
+        unleased.lease_expires_at = None;
+        jobs.update(&unleased).await.unwrap();
+
+        let root = env::temp_dir().join(format!("recovery-{}", Uuid::new_v4()));
+        let recovery = JobRecovery {
+            workspaces: WorkspaceManager::new(&root),
+            ..JobRecovery::new(jobs.clone())
+        };
+
+        // O worker reiniciou logo depois do crash: o lease expirado basta
+        let report = recovery
+            .recover(now + chrono::Duration::minutes(10))
+            .await
+            .expect("Failed to recover");
+        assert_eq!(report.requeued, vec![claimed[0].id]);
+        assert_eq!(
+            jobs.find(&claimed[1].id).await.unwrap().status,
+            JobStatus::Fragmenting
+        );
+
+        let report = recovery
+            .recover(now + chrono::Duration::hours(2))
+            .await
+            .expect("Failed to recover");
+        assert_eq!(report.requeued, vec![claimed[1].id]);
+    }
+}
@@ -0,0 +1,790 @@
+use std::{env, time::Duration};
+
+use anyhow::Context;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::video_service::DEFAULT_UPLOAD_CONCURRENCY;
+use crate::{
+    application::{
+        JobClaimer, JobRepositoryError, Repository, RetryPolicy, VideoRepositoryError,
+        VideoService, WorkspaceManager,
+    },
+    config::Config,
+    domain::{Job, JobStatus, Video},
+    framework::{ObjectStore, ObjectStoreError},
+};
+
+const DEFAULT_LEASE: chrono::Duration = chrono::Duration::minutes(5);
+
+/// Erro de um job junto com o estágio em que ele ocorreu
+#[derive(Debug)]
+pub struct StageFailure {
+    pub stage: JobStatus,
+    pub error: anyhow::Error,
+}
+
+impl StageFailure {
+    /// O vídeo de origem não existe no bucket, então novas tentativas não vão resolver
+    pub fn is_source_missing(&self) -> bool {
+        self.error.chain().any(|e| {
+            // Um nome inválido também nunca vai existir no bucket
+            matches!(
+                e.downcast_ref::<ObjectStoreError>(),
+                Some(ObjectStoreError::NotFound { .. } | ObjectStoreError::InvalidName { .. })
+            )
+        })
+    }
+}
+
+/// A reivindicação do job passou para outro worker enquanto os estágios
+/// executavam. A tentativa é abandonada sem gravar nada no job
+#[derive(Debug)]
+pub struct LeaseLost {
+    pub job_id: Uuid,
+    pub worker_id: String,
+}
+
+impl std::fmt::Display for LeaseLost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "job {} is no longer held by worker {}",
+            self.job_id, self.worker_id
+        )
+    }
+}
+
+impl std::error::Error for LeaseLost {}
+
+/// Executa um job de ponta a ponta: download → fragment → encode → upload → finish,
+/// persistindo o status e os checkpoints a cada transição. Falhas recuperáveis
+/// deixam o job em retrying até a próxima tentativa, conforme a `retry_policy`,
+/// e a nova tentativa pula os estágios cujos artefatos ainda estão íntegros.
+/// Cada job usa o próprio workspace, mantido no disco enquanto houver tentativas.
+/// Enquanto os estágios executam, a reivindicação do job é renovada a cada
+/// terço do `lease`
+pub struct JobWorker<VR, JR, S>
+where
+    VR: Repository<Video, Error = VideoRepositoryError> + Clone,
+    JR: Repository<Job, Error = JobRepositoryError>,
+    S: ObjectStore + Clone,
+{
+    pub video_repository: VR,
+    pub job_repository: JR,
+    pub store: S,
+    pub input_bucket_name: String,
+    pub retry_policy: RetryPolicy,
+    pub workspaces: WorkspaceManager,
+    pub upload_concurrency: usize,
+    /// Worker registrado nos jobs iniciados por `process`; `process_next`
+    /// registra o id recebido
+    pub worker_id: String,
+    /// Duração de cada reivindicação e de cada renovação
+    pub lease: chrono::Duration,
+}
+
+impl<VR, JR, S> JobWorker<VR, JR, S>
+where
+    VR: Repository<Video, Error = VideoRepositoryError> + Clone,
+    JR: Repository<Job, Error = JobRepositoryError> + JobClaimer,
+    S: ObjectStore + Clone,
+{
+    pub fn new(
+        video_repository: VR,
+        job_repository: JR,
+        store: S,
+        input_bucket_name: String,
+    ) -> Self {
+        JobWorker {
+            video_repository,
+            job_repository,
+            store,
+            input_bucket_name,
+            retry_policy: RetryPolicy::default(),
+            workspaces: WorkspaceManager::new(env::temp_dir()),
+            upload_concurrency: DEFAULT_UPLOAD_CONCURRENCY,
+            worker_id: unique_worker_id("worker"),
+            lease: DEFAULT_LEASE,
+        }
+    }
+
+    pub fn from_config(
+        video_repository: VR,
+        job_repository: JR,
+        store: S,
+        config: &Config,
+    ) -> Self {
+        JobWorker {
+            video_repository,
+            job_repository,
+            store,
+            input_bucket_name: config.storage.input_bucket_name.clone(),
+            retry_policy: RetryPolicy::from_config(&config.jobs),
+            workspaces: WorkspaceManager::from_config(&config.storage),
+            upload_concurrency: config.storage.upload_concurrency,
+            worker_id: unique_worker_id(&config.rabbitmq.consumer_name),
+            lease: chrono::Duration::from_std(config.jobs.lease).unwrap_or(DEFAULT_LEASE),
+        }
+    }
+
+    /// Processa o job e retorna seu estado ao fim da tentativa (completed,
+    /// retrying ou failed).
+    /// Só retorna erro quando não é possível registrar a falha no banco
+    pub async fn process(&self, job: Job) -> anyhow::Result<Job> {
+        self.execute(job).await.map(|(job, _)| job)
+    }
+
+    /// Como `process`, mas também retorna o estágio e o erro quando o job falha.
+    /// Retorna `LeaseLost` se outro worker assumir o job no meio da tentativa
+    pub async fn execute(&self, mut job: Job) -> anyhow::Result<(Job, Option<StageFailure>)> {
+        let mut video_service = VideoService::new(
+            self.video_repository.clone(),
+            job.video.as_ref().clone(),
+            self.store.clone(),
+            self.workspaces.workspace(job.id, job.video_id),
+        );
+        video_service.upload_concurrency = self.upload_concurrency;
+
+        let failure = match self.run_stages(&mut job, &video_service).await {
+            Ok(()) => None,
+            Err(error) if error.is::<LeaseLost>() => {
+                // O novo dono pode estar neste mesmo host e reaproveitar os arquivos
+                video_service.workspace.keep();
+                return Err(error);
+            }
+            Err(error) => {
+                let message = format!("{:#}", error);
+                tracing::error!("Job {} failed while {}: {}", job.id, job.status, message);
+
+                let failure = StageFailure {
+                    stage: job.status,
+                    error,
+                };
+
+                // O vídeo de origem inexistente não se resolve com novas tentativas
+                let retry_at = (!failure.is_source_missing())
+                    .then(|| self.retry_policy.next_attempt_at(job.attempts, Utc::now()));
+
+                job.fail_attempt(message, retry_at)?;
+                job = self.job_repository.update(&job).await?;
+
+                match job.next_attempt_at {
+                    Some(retry_at) => {
+                        // Os artefatos verificados pelos checkpoints são reaproveitados
+                        video_service.workspace.keep();
+                        tracing::warn!(
+                            "Job {} will be retried at {} (attempt {}/{})",
+                            job.id,
+                            retry_at,
+                            job.attempts,
+                            job.max_attempts
+                        );
+                    }
+                    None => video_service.workspace.cleanup().await,
+                }
+
+                Some(failure)
+            }
+        };
+
+        Ok((job, failure))
+    }
+
+    async fn run_stages(
+        &self,
+        job: &mut Job,
+        video_service: &VideoService<VR, S>,
+    ) -> anyhow::Result<()> {
+        // Jobs reivindicados via claim_next já chegam em downloading
+        if job.status != JobStatus::Downloading {
+            job.claim(self.worker_id.clone(), self.lease)?;
+            *job = self.job_repository.update(job).await?;
+
+            tracing::info!("Job {} started attempt {}", job.id, job.attempts);
+        }
+
+        // Sem a renovação um job longo pareceria abandonado para a recuperação
+        let heartbeat = self.renew_lease(job.id, job.worker_id.clone());
+        let stages = self.run_claimed_stages(job, video_service);
+
+        // Sem a reivindicação os estágios são interrompidos: o job já pode
+        // estar com outro worker
+        tokio::select! {
+            result = stages => result,
+            lost = heartbeat => Err(lost.into()),
+        }
+    }
+
+    async fn run_claimed_stages(
+        &self,
+        job: &mut Job,
+        video_service: &VideoService<VR, S>,
+    ) -> anyhow::Result<()> {
+        // Cada checkpoint é gravado junto com a transição para o estágio seguinte
+        match &job.checkpoints.downloaded {
+            Some(checkpoint) if video_service.has_source(checkpoint).await => {
+                tracing::info!("Job {} reusing the downloaded source video", job.id);
+            }
+            _ => {
+                let checkpoint = video_service
+                    .download(&self.input_bucket_name)
+                    .await
+                    .context("failed to download source video")?;
+                job.checkpoints.record_download(checkpoint);
+            }
+        }
+
+        self.transition(job, JobStatus::Fragmenting).await?;
+        match &job.checkpoints.fragmented {
+            Some(checkpoint) if video_service.has_fragment(checkpoint).await => {
+                tracing::info!("Job {} reusing the fragmented video", job.id);
+            }
+            _ => {
+                let checkpoint = video_service
+                    .fragment()
+                    .await
+                    .context("failed to fragment video")?;
+                job.checkpoints.record_fragment(checkpoint);
+            }
+        }
+
+        self.transition(job, JobStatus::Encoding).await?;
+        match &job.checkpoints.encoded {
+            Some(checkpoint) if video_service.has_encoded_output(checkpoint).await => {
+                tracing::info!("Job {} reusing the encoded output", job.id);
+            }
+            _ => {
+                let checkpoint = video_service
+                    .encode()
+                    .await
+                    .context("failed to encode video")?;
+                job.checkpoints.record_encode(checkpoint);
+            }
+        }
+
+        // Objetos enviados ficam nos checkpoints mesmo se o upload falhar,
+        // para que a próxima tentativa envie só os que faltam
+        self.transition(job, JobStatus::Uploading).await?;
+        video_service
+            .upload(&job.output_bucket_path, &mut job.checkpoints)
+            .await
+            .context("failed to upload encoded video")?;
+
+        self.transition(job, JobStatus::Finishing).await?;
+        video_service
+            .finish()
+            .await
+            .context("failed to clean up local files")?;
+
+        self.transition(job, JobStatus::Completed).await?;
+
+        Ok(())
+    }
+
+    /// Aplica a transição no domínio e persiste o job. A reivindicação é
+    /// estendida junto, para não sobrescrever a renovada em segundo plano
+    async fn transition(&self, job: &mut Job, status: JobStatus) -> anyhow::Result<()> {
+        job.transition_to(status)?;
+        job.renew_lease(self.lease);
+        *job = self.job_repository.update(job).await?;
+
+        tracing::info!("Job {} is now {}", job.id, job.status);
+
+        Ok(())
+    }
+
+    /// Renova a reivindicação do job periodicamente. Só retorna quando o job
+    /// deixa de pertencer ao worker; outros erros são registrados e a
+    /// renovação continua no próximo ciclo
+    async fn renew_lease(&self, job_id: Uuid, worker_id: Option<String>) -> LeaseLost {
+        let Some(worker_id) = worker_id else {
+            return std::future::pending().await;
+        };
+        let interval = (self.lease / 3)
+            .to_std()
+            .unwrap_or_default()
+            .max(Duration::from_millis(10));
+
+        loop {
+            tokio::time::sleep(interval).await;
+
+            match self
+                .job_repository
+                .renew_lease(&job_id, &worker_id, Utc::now() + self.lease)
+                .await
+            {
+                Ok(()) => tracing::debug!("Renewed lease of job {}", job_id),
+                Err(e) if e.is_not_found() => return LeaseLost { job_id, worker_id },
+                Err(e) => tracing::warn!("Failed to renew lease of job {}: {}", job_id, e),
+            }
+        }
+    }
+
+    /// Reivindica o próximo job pendente e o processa.
+    /// Retorna `None` quando não há jobs pendentes
+    pub async fn process_next(&self, worker_id: &str) -> anyhow::Result<Option<Job>> {
+        match self
+            .job_repository
+            .claim_next(worker_id, self.lease)
+            .await?
+        {
+            Some(job) => {
+                tracing::info!("Worker {} claimed job {}", worker_id, job.id);
+                self.process(job).await.map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Id de worker único entre processos e hosts: `{prefix}-{hostname}-{uuid}`.
+/// O pid não serve, já que nos containers ele costuma ser sempre 1
+pub fn unique_worker_id(prefix: &str) -> String {
+    format!("{}-{}-{}", prefix, hostname(), Uuid::new_v4())
+}
+
+fn hostname() -> String {
+    // Nos containers o HOSTNAME é o id do container
+    env::var("HOSTNAME")
+        .ok()
+        .filter(|hostname| !hostname.is_empty())
+        .or_else(system_hostname)
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+#[cfg(unix)]
+fn system_hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+
+    // SAFETY: o buffer é válido para escrita em todo o tamanho informado
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+        return None;
+    }
+
+    std::ffi::CStr::from_bytes_until_nul(&buf)
+        .ok()?
+        .to_str()
+        .ok()
+        .filter(|hostname| !hostname.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(not(unix))]
+fn system_hostname() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, sync::Arc};
+
+    use super::*;
+    use crate::{
+        application::InMemoryDatabase,
+        framework::{GcsObjectStore, LocalObjectStore, ObjectReader, StoredObject},
+    };
+
+    async fn insert_job(db: &InMemoryDatabase, video: Video) -> Job {
+        db.videos()
+            .insert(&video)
+            .await
+            .expect("Failed to insert video");
+
+        let job = Job::new("codeeducationtest".to_string(), Arc::new(video));
+        db.jobs().insert(&job).await.expect("Failed to insert job");
+
+        job
+    }
+
+    #[test]
+    fn test_unique_worker_id_differs_between_workers() {
+        let (first, second) = (unique_worker_id("encoder"), unique_worker_id("encoder"));
+
+        assert!(first.starts_with(&format!("encoder-{}-", hostname())));
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_job_worker_rejects_finished_job() {
+        let db = InMemoryDatabase::new();
+        let mut job = insert_job(
+            &db,
+            Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string()),
+        )
+        .await;
+
+        let job_repo = db.jobs();
+        job.status = JobStatus::Completed;
+        let job = job_repo.update(&job).await.expect("Failed to update job");
+
+        let worker = JobWorker::new(
+            db.videos(),
+            db.jobs(),
+            LocalObjectStore::new(env::temp_dir()),
+            "bucket".to_string(),
+        );
+        let result = worker.process(job.clone()).await;
+
+        assert!(result.is_err());
+
+        let found_job = job_repo.find(&job.id).await.expect("Failed to find job");
+        assert_eq!(found_job.status, JobStatus::Completed);
+        assert_eq!(found_job.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_job_worker_marks_job_failed_when_source_is_missing() {
+        let db = InMemoryDatabase::new();
+        let job = insert_job(
+            &db,
+            Video::new("resource_123".to_string(), "videos/missing.mp4".to_string()),
+        )
+        .await;
+
+        let root = env::temp_dir().join(format!("store-{}", uuid::Uuid::new_v4()));
+        let worker = JobWorker::new(
+            db.videos(),
+            db.jobs(),
+            LocalObjectStore::new(&root),
+            "input".to_string(),
+        );
+
+        let (processed, failure) = worker.execute(job).await.expect("Failed to process job");
+
+        assert_eq!(processed.status, JobStatus::Failed);
+        let failure = failure.expect("Failure should be reported");
+        assert_eq!(failure.stage, JobStatus::Downloading);
+        assert!(failure.is_source_missing());
+
+        let error = processed.error.clone().expect("Job should have an error");
+        assert!(error.starts_with("failed to download source video"));
+
+        let found_job = db
+            .jobs()
+            .find(&processed.id)
+            .await
+            .expect("Failed to find job");
+        assert_eq!(found_job.status, JobStatus::Failed);
+        assert_eq!(found_job.error, Some(error));
+    }
+
+    #[tokio::test]
+    async fn test_job_worker_schedules_retry_on_transient_failure() {
+        let db = InMemoryDatabase::new();
+        let job = insert_job(
+            &db,
+            Video::new("resource_123".to_string(), "videos/source.mp4".to_string()),
+        )
+        .await;
+
+        // A raiz do store é um arquivo, então a leitura falha com um erro de IO
+        // que não é "not found" e pode ser tentado novamente
+        let root = env::temp_dir().join(format!("store-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&root, b"").await.unwrap();
+
+        let worker = JobWorker::new(
+            db.videos(),
+            db.jobs(),
+            LocalObjectStore::new(&root),
+            "input".to_string(),
+        );
+        let before = Utc::now();
+
+        let (processed, failure) = worker.execute(job).await.expect("Failed to process job");
+
+        assert!(
+            !failure
+                .expect("Failure should be reported")
+                .is_source_missing()
+        );
+        assert_eq!(processed.status, JobStatus::Retrying);
+        assert_eq!(processed.attempts, 1);
+        assert!(processed.next_attempt_at.unwrap() > before);
+
+        let found_job = db.jobs().find(&processed.id).await.unwrap();
+        assert_eq!(found_job.status, JobStatus::Retrying);
+        assert_eq!(found_job.attempt_errors.len(), 1);
+        assert_eq!(found_job.attempt_errors[0].stage, JobStatus::Downloading);
+
+        tokio::fs::remove_file(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_job_worker_resumes_from_verified_checkpoint() {
+        let db = InMemoryDatabase::new();
+        let mut job = insert_job(
+            &db,
+            Video::new("resource_123".to_string(), "videos/source.mp4".to_string()),
+        )
+        .await;
+
+        // Uma tentativa anterior baixou o vídeo, que depois saiu do bucket
+        let root = env::temp_dir().join(format!("store-{}", uuid::Uuid::new_v4()));
+        let store = LocalObjectStore::new(&root);
+        store
+            .put("input", "videos/source.mp4", b"fake mp4".to_vec())
+            .await
+            .unwrap();
+
+        let workspaces =
+            WorkspaceManager::new(env::temp_dir().join(format!("workspaces-{}", job.id)));
+        let mut workspace = workspaces.workspace(job.id, job.video_id);
+        workspace.keep();
+
+        let video_service = VideoService::new(
+            db.videos(),
+            job.video.as_ref().clone(),
+            store.clone(),
+            workspace,
+        );
+        let checkpoint = video_service.download("input").await.unwrap();
+        store.delete("input", "videos/source.mp4").await.unwrap();
+
+        job.checkpoints.record_download(checkpoint);
+        let job = db.jobs().update(&job).await.unwrap();
+
+        let worker = JobWorker {
+            workspaces: workspaces.clone(),
+            ..JobWorker::new(db.videos(), db.jobs(), store, "input".to_string())
+        };
+        let (processed, failure) = worker.execute(job).await.expect("Failed to process job");
+
+        // O download é pulado e o job segue até o mp4fragment, que não está
+        // disponível nos testes
+        let failure = failure.expect("Failure should be reported");
+        assert_eq!(failure.stage, JobStatus::Fragmenting);
+        assert_eq!(processed.status, JobStatus::Retrying);
+        assert!(processed.checkpoints.downloaded.is_some());
+        assert!(processed.checkpoints.fragmented.is_none());
+
+        // O workspace fica no disco para a próxima tentativa
+        let downloaded = processed.checkpoints.downloaded.as_ref().unwrap();
+        assert!(video_service.has_source(downloaded).await);
+
+        tokio::fs::remove_dir_all(&workspaces.root).await.unwrap();
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_job_worker_process_next_claims_pending_job() {
+        let db = InMemoryDatabase::new();
+        let root = env::temp_dir().join(format!("store-{}", uuid::Uuid::new_v4()));
+        let worker = JobWorker::new(
+            db.videos(),
+            db.jobs(),
+            LocalObjectStore::new(&root),
+            "input".to_string(),
+        );
+
+        let processed = worker
+            .process_next("worker-1")
+            .await
+            .expect("Failed to process next job");
+        assert!(processed.is_none());
+
+        let job = insert_job(
+            &db,
+            Video::new("resource_123".to_string(), "videos/missing.mp4".to_string()),
+        )
+        .await;
+
+        let processed = worker
+            .process_next("worker-1")
+            .await
+            .expect("Failed to process next job")
+            .expect("Pending job should be claimed");
+
+        // O job reivindicado segue o fluxo normal a partir do download
+        assert_eq!(processed.id, job.id);
+        assert_eq!(processed.status, JobStatus::Failed);
+        assert_eq!(processed.worker_id.as_deref(), Some("worker-1"));
+        assert!(
+            processed
+                .error
+                .unwrap()
+                .starts_with("failed to download source video")
+        );
+    }
+
+    /// Store que demora a responder e então falha, simulando um download longo
+    #[derive(Clone)]
+    struct SlowStore(Duration);
+
+    impl ObjectStore for SlowStore {
+        async fn get(&self, _bucket: &str, _object: &str) -> Result<Vec<u8>, ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+
+        async fn get_stream(
+            &self,
+            _bucket: &str,
+            _object: &str,
+        ) -> Result<ObjectReader, ObjectStoreError> {
+            tokio::time::sleep(self.0).await;
+            Err(ObjectStoreError::Backend("timed out".to_string()))
+        }
+
+        async fn put(
+            &self,
+            _bucket: &str,
+            _object: &str,
+            _data: Vec<u8>,
+        ) -> Result<StoredObject, ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+
+        async fn put_stream(
+            &self,
+            _bucket: &str,
+            _object: &str,
+            _reader: ObjectReader,
+        ) -> Result<StoredObject, ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+
+        async fn list(
+            &self,
+            _bucket: &str,
+            _prefix: &str,
+        ) -> Result<Vec<StoredObject>, ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+
+        async fn delete(&self, _bucket: &str, _object: &str) -> Result<(), ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job_worker_renews_lease_while_stages_run() {
+        let db = InMemoryDatabase::new();
+        let job = insert_job(
+            &db,
+            Video::new("resource_123".to_string(), "videos/source.mp4".to_string()),
+        )
+        .await;
+
+        // O job recebido sem reivindicação também ganha worker e lease
+        let worker = JobWorker {
+            lease: chrono::Duration::milliseconds(300),
+            ..JobWorker::new(
+                db.videos(),
+                db.jobs(),
+                SlowStore(Duration::from_millis(600)),
+                "input".to_string(),
+            )
+        };
+
+        let jobs = db.jobs();
+        let observe = async {
+            let started = loop {
+                let found = jobs.find(&job.id).await.unwrap();
+                if found.status == JobStatus::Downloading {
+                    break found;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            };
+            assert_eq!(
+                started.worker_id.as_deref(),
+                Some(worker.worker_id.as_str())
+            );
+
+            // O download ainda está em andamento, mas a reivindicação avançou
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            let renewed = jobs.find(&job.id).await.unwrap();
+            assert_eq!(renewed.status, JobStatus::Downloading);
+            assert!(renewed.lease_expires_at.unwrap() > started.lease_expires_at.unwrap());
+        };
+
+        let (result, ()) = tokio::join!(worker.execute(job.clone()), observe);
+        let (processed, _) = result.expect("Failed to process job");
+
+        assert_eq!(processed.status, JobStatus::Retrying);
+        assert_eq!(processed.lease_expires_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_job_worker_abandons_attempt_when_lease_is_lost() {
+        let db = InMemoryDatabase::new();
+        let job = insert_job(
+            &db,
+            Video::new("resource_123".to_string(), "videos/source.mp4".to_string()),
+        )
+        .await;
+
+        let worker = JobWorker {
+            lease: chrono::Duration::milliseconds(300),
+            ..JobWorker::new(
+                db.videos(),
+                db.jobs(),
+                SlowStore(Duration::from_secs(5)),
+                "input".to_string(),
+            )
+        };
+
+        // Outro worker assume o job no meio do download
+        let jobs = db.jobs();
+        let take_over = async {
+            let mut found = loop {
+                let found = jobs.find(&job.id).await.unwrap();
+                if found.status == JobStatus::Downloading {
+                    break found;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            };
+            found.worker_id = Some("worker-2".to_string());
+            jobs.update(&found).await.unwrap();
+        };
+
+        let started = std::time::Instant::now();
+        let (result, ()) = tokio::join!(worker.execute(job.clone()), take_over);
+
+        // O download é interrompido sem esperar o store
+        let error = result.expect_err("Lost lease should abort the attempt");
+        assert!(error.is::<LeaseLost>());
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let found = jobs.find(&job.id).await.unwrap();
+        assert_eq!(found.status, JobStatus::Downloading);
+        assert_eq!(found.worker_id.as_deref(), Some("worker-2"));
+        assert!(found.attempt_errors.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_job_worker_process() {
+        let db = InMemoryDatabase::new();
+        let job = insert_job(
+            &db,
+            Video::new(
+                "3fa3291e-5daf-4386-9a67-69d19e1690c5".to_string(),
+                "videos/3fa3291e-5daf-4386-9a67-69d19e1690c5/videos/3fa3291e-5daf-4386-9a67-69d19e1690c5-b8c187dd77c950e9b117bcc19e35a9005e45001593f7f4260040cee47d77faa0.mp4".to_string(),
+            ),
+        )
+        .await;
+
+        let store = GcsObjectStore::from_env()
+            .await
+            .expect("Failed to create GCS client");
+        let worker = JobWorker {
+            workspaces: WorkspaceManager::new("./tmp"),
+            ..JobWorker::new(
+                db.videos(),
+                db.jobs(),
+                store,
+                "micro-admin-typescript-josemoura212".to_string(),
+            )
+        };
+        let processed = worker.process(job).await.expect("Failed to process job");
+
+        assert_eq!(processed.status, JobStatus::Completed);
+
+        let found_job = db
+            .jobs()
+            .find(&processed.id)
+            .await
+            .expect("Failed to find job");
+        assert_eq!(found_job.status, JobStatus::Completed);
+    }
+}
@@ -0,0 +1,234 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    application::{JobFilter, JobRepositoryError, Pagination, Repository},
+    config::JobsConfig,
+    domain::{Job, JobStatus},
+};
+
+const DEFAULT_BASE_DELAY: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30 * 60);
+const DEFAULT_JITTER: f64 = 0.2;
+const DEFAULT_SCHEDULER_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Backoff exponencial com jitter entre as tentativas de um job:
+/// `base_delay * 2^(tentativa - 1)`, limitado a `max_delay` e variando
+/// aleatoriamente até `jitter` (fração do atraso) para mais ou para menos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            jitter: DEFAULT_JITTER,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &JobsConfig) -> Self {
+        RetryPolicy {
+            base_delay: config.retry_base_delay,
+            max_delay: config.retry_max_delay,
+            jitter: config.retry_jitter,
+        }
+    }
+
+    /// Atraso antes da tentativa seguinte à tentativa `attempt` (a partir de 1)
+    pub fn delay_for(&self, attempt: i32) -> Duration {
+        let exponent = attempt.saturating_sub(1).clamp(0, 31) as u32;
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(exponent))
+            .min(self.max_delay);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return delay;
+        }
+
+        delay.mul_f64(1.0 + rand::random_range(-jitter..=jitter))
+    }
+
+    pub fn next_attempt_at(&self, attempt: i32, now: DateTime<Utc>) -> DateTime<Utc> {
+        let delay =
+            chrono::Duration::from_std(self.delay_for(attempt)).unwrap_or(chrono::Duration::MAX);
+
+        now.checked_add_signed(delay)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+/// Devolve para pending os jobs em retrying cuja próxima tentativa venceu,
+/// para que os workers os reivindiquem de novo via `claim_next`
+pub struct RetryScheduler<JR>
+where
+    JR: Repository<Job, Error = JobRepositoryError, Filter = JobFilter>,
+{
+    pub job_repository: JR,
+    pub interval: Duration,
+}
+
+impl<JR> RetryScheduler<JR>
+where
+    JR: Repository<Job, Error = JobRepositoryError, Filter = JobFilter>,
+{
+    pub fn new(job_repository: JR) -> Self {
+        RetryScheduler {
+            job_repository,
+            interval: DEFAULT_SCHEDULER_INTERVAL,
+        }
+    }
+
+    pub fn from_config(job_repository: JR, config: &JobsConfig) -> Self {
+        RetryScheduler {
+            job_repository,
+            interval: config.scheduler_interval,
+        }
+    }
+
+    /// Reenfileira os jobs vencidos até `now`, retornando os que voltaram para pending.
+    /// Jobs alterados por outro processo no meio do caminho são ignorados
+    pub async fn requeue_due(&self, now: DateTime<Utc>) -> Result<Vec<Job>, JobRepositoryError> {
+        let filter = JobFilter {
+            status: Some(JobStatus::Retrying),
+            next_attempt_before: Some(now),
+            ..Default::default()
+        };
+
+        // Lê todas as páginas antes de atualizar, já que os jobs reenfileirados
+        // deixam de atender o filtro e deslocariam os offsets
+        let mut due = Vec::new();
+        let mut pagination = Some(Pagination::default());
+        while let Some(current) = pagination {
+            let page = self.job_repository.list(&filter, current).await?;
+            due.extend(page.items);
+            pagination = page.next;
+        }
+
+        let mut requeued = Vec::with_capacity(due.len());
+        for mut job in due {
+            if job.requeue().is_err() {
+                continue;
+            }
+
+            match self.job_repository.update(&job).await {
+                Ok(job) => {
+                    tracing::info!("Job {} requeued for attempt {}", job.id, job.attempts + 1);
+                    requeued.push(job);
+                }
+                Err(e) if e.is_version_conflict() || e.is_not_found() => {
+                    tracing::debug!("Job {} changed before it could be requeued: {}", job.id, e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(requeued)
+    }
+
+    /// Executa `requeue_due` a cada `interval`. Erros são registrados e a
+    /// próxima rodada tenta novamente
+    pub async fn run(&self) {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = self.requeue_due(Utc::now()).await {
+                tracing::error!("Failed to requeue jobs awaiting retry: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        application::{InMemoryDatabase, JobClaimer},
+        domain::Video,
+    };
+
+    #[test]
+    fn test_retry_policy_backs_off_exponentially_with_jitter() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_secs(10),
+            max_delay: Duration::from_secs(60),
+            jitter: 0.0,
+        };
+
+        let delays: Vec<_> = (1..=5).map(|attempt| policy.delay_for(attempt)).collect();
+        assert_eq!(
+            delays,
+            [10, 20, 40, 60, 60].map(Duration::from_secs).to_vec()
+        );
+        assert_eq!(policy.delay_for(1000), Duration::from_secs(60));
+
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        for _ in 0..100 {
+            let delay = policy.delay_for(2);
+            assert!(delay >= Duration::from_secs(10) && delay <= Duration::from_secs(30));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_scheduler_requeues_only_due_jobs() {
+        let db = InMemoryDatabase::new();
+        let jobs = db.jobs();
+        let now = Utc::now();
+
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        db.videos().insert(&video).await.unwrap();
+
+        let mut inserted = Vec::new();
+        for retry_in in [-1, 60] {
+            let mut job = Job::new("output".to_string(), Arc::new(video.clone()));
+            job.start().unwrap();
+            job.fail_attempt(
+                "upload failed",
+                Some(now + chrono::Duration::seconds(retry_in)),
+            )
+            .unwrap();
+            jobs.insert(&job).await.expect("Failed to insert job");
+            inserted.push(job);
+        }
+
+        let scheduler = RetryScheduler::new(jobs.clone());
+        let requeued = scheduler
+            .requeue_due(now)
+            .await
+            .expect("Failed to requeue jobs");
+
+        assert_eq!(requeued.len(), 1);
+        assert_eq!(requeued[0].id, inserted[0].id);
+        assert_eq!(requeued[0].status, JobStatus::Pending);
+
+        let waiting = jobs.find(&inserted[1].id).await.unwrap();
+        assert_eq!(waiting.status, JobStatus::Retrying);
+
+        // O job reenfileirado é reivindicado como uma nova tentativa
+        let claimed = jobs
+            .claim_next("worker-1", chrono::Duration::minutes(5))
+            .await
+            .unwrap()
+            .expect("Requeued job should be claimed");
+        assert_eq!(claimed.id, inserted[0].id);
+        assert_eq!(claimed.attempts, 2);
+        assert_eq!(claimed.attempt_errors.len(), 1);
+    }
+}
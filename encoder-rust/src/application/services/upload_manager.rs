@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+use futures::StreamExt;
+
+use crate::framework::storage::{ObjectStore, StoredObject, collect_files, open_file};
+
+#[derive(Debug, Clone)]
+pub struct UploadFailure {
+    pub object: String,
+    pub error: String,
+}
+
+/// Resultado de um upload: objetos confirmados no bucket e falhas por arquivo
+#[derive(Debug, Clone, Default)]
+pub struct UploadReport {
+    pub uploaded: Vec<StoredObject>,
+    pub failures: Vec<UploadFailure>,
+}
+
+impl UploadReport {
+    /// O upload só é considerado completo quando todos os objetos foram confirmados
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn total(&self) -> usize {
+        self.uploaded.len() + self.failures.len()
+    }
+}
+
+/// Publica um diretório local no bucket de saída com um número limitado de uploads simultâneos
+pub struct UploadManager<S>
+where
+    S: ObjectStore,
+{
+    store: S,
+    bucket_name: String,
+    concurrency: usize,
+}
+
+impl<S> UploadManager<S>
+where
+    S: ObjectStore,
+{
+    pub fn new(store: S, bucket_name: String, concurrency: usize) -> Self {
+        UploadManager {
+            store,
+            bucket_name,
+            concurrency: concurrency.max(1),
+        }
+    }
+
+    /// Envia todos os arquivos de `dir`, usando o caminho relativo a `base_path` como nome do objeto
+    pub async fn upload_dir(&self, base_path: &Path, dir: &Path) -> anyhow::Result<UploadReport> {
+        let files = collect_files(dir).await?;
+
+        Ok(self.upload_files(base_path, files).await)
+    }
+
+    /// Envia os arquivos informados, usando o caminho relativo a `base_path` como nome do objeto
+    pub async fn upload_files(&self, base_path: &Path, files: Vec<PathBuf>) -> UploadReport {
+        let results: Vec<_> = futures::stream::iter(files)
+            .map(|path| async move {
+                let object = object_name(base_path, &path);
+                let result = self.upload_file(&path, &object).await;
+                (object, result)
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut report = UploadReport::default();
+
+        for (object, result) in results {
+            match result {
+                Ok(uploaded) => {
+                    tracing::info!("Uploaded {} to bucket {}", object, self.bucket_name);
+                    report.uploaded.push(uploaded);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to upload {}: {:#}", object, e);
+                    report.failures.push(UploadFailure {
+                        object,
+                        error: format!("{:#}", e),
+                    });
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Envia um arquivo em streaming e confirma que o objeto gravado tem o mesmo tamanho do arquivo local
+    async fn upload_file(&self, path: &Path, object: &str) -> anyhow::Result<StoredObject> {
+        let reader = open_file(path).await?;
+        let size = reader.size;
+
+        let uploaded = self
+            .store
+            .put_stream(&self.bucket_name, object, reader)
+            .await?;
+
+        if uploaded.size != size {
+            anyhow::bail!(
+                "object {} has {} bytes, expected {}",
+                object,
+                uploaded.size,
+                size
+            );
+        }
+
+        Ok(uploaded)
+    }
+}
+
+pub(super) fn object_name(base_path: &Path, path: &Path) -> String {
+    path.strip_prefix(base_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::storage::LocalObjectStore;
+
+    #[tokio::test]
+    async fn test_upload_manager_uploads_every_file() {
+        let base = std::env::temp_dir().join(format!("upload-{}", uuid::Uuid::new_v4()));
+        let output = base.join("local/video-id");
+
+        tokio::fs::create_dir_all(output.join("video/avc1"))
+            .await
+            .expect("Failed to create output directory");
+        tokio::fs::write(output.join("stream.mpd"), b"<MPD/>")
+            .await
+            .unwrap();
+        tokio::fs::write(output.join("video/avc1/seg-1.m4s"), b"segment")
+            .await
+            .unwrap();
+
+        let store = LocalObjectStore::new(base.join("buckets"));
+        let manager = UploadManager::new(store.clone(), "output".to_string(), 2);
+
+        let report = manager
+            .upload_dir(&base.join("local"), &output)
+            .await
+            .expect("Failed to upload directory");
+
+        assert!(report.is_complete());
+        assert_eq!(report.total(), 2);
+
+        let objects: Vec<_> = store
+            .list("output", "video-id/")
+            .await
+            .expect("Failed to list objects")
+            .into_iter()
+            .map(|o| o.name)
+            .collect();
+
+        assert_eq!(
+            objects,
+            vec!["video-id/stream.mpd", "video-id/video/avc1/seg-1.m4s"]
+        );
+
+        tokio::fs::remove_dir_all(&base).await.unwrap();
+    }
+}
@@ -1,125 +1,340 @@
-use std::env;
-use std::path::PathBuf;
+use chrono::Utc;
+use futures::StreamExt;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use super::upload_manager::object_name;
+use crate::{
+    application::{EncodingError, Repository, UploadManager, VideoRepositoryError, Workspace},
+    domain::{EncodeCheckpoint, FileCheckpoint, JobCheckpoints, OutputFile, Video},
+    framework::{ObjectStore, storage::collect_files},
+};
+
+pub(super) const DEFAULT_UPLOAD_CONCURRENCY: usize = 50;
+
+// O vídeo de origem, o fragmentado e a saída do mp4dash ficam no disco ao
+// mesmo tempo, cada um com aproximadamente o tamanho da origem
+const REQUIRED_SPACE_FACTOR: u64 = 3;
+
+/// Progresso de um download: bytes recebidos vs. tamanho do objeto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub received: u64,
+    pub total: u64,
+}
+
+impl DownloadProgress {
+    pub fn percent(&self) -> u64 {
+        if self.total == 0 {
+            return 100;
+        }
 
-use crate::{application::VideoRepository, domain::Video};
-use google_cloud_storage::client::{Client, ClientConfig};
-use google_cloud_storage::http::objects::download::Range;
-use google_cloud_storage::http::objects::get::GetObjectRequest;
+        self.received * 100 / self.total
+    }
+}
 
-pub struct VideoService<DB>
+pub struct VideoService<R, S>
 where
-    DB: sqlx::Database,
+    R: Repository<Video, Error = VideoRepositoryError>,
+    S: ObjectStore + Clone,
 {
-    pub video_repository: VideoRepository<DB>,
+    pub video_repository: R,
     pub video: Video,
+    pub store: S,
+    pub workspace: Workspace,
+    /// Quantidade máxima de uploads simultâneos
+    pub upload_concurrency: usize,
 }
 
-impl<DB> VideoService<DB>
+impl<R, S> VideoService<R, S>
 where
-    DB: sqlx::Database,
+    R: Repository<Video, Error = VideoRepositoryError>,
+    S: ObjectStore + Clone,
 {
-    pub fn new(video_repository: VideoRepository<DB>, video: Video) -> Self {
+    pub fn new(video_repository: R, video: Video, store: S, workspace: Workspace) -> Self {
         VideoService {
             video_repository,
             video,
+            store,
+            workspace,
+            upload_concurrency: DEFAULT_UPLOAD_CONCURRENCY,
         }
     }
 
-    pub async fn download(&self, bucket_name: &str) -> anyhow::Result<()> {
-        let config = ClientConfig::default().with_auth().await?;
-        let client = Client::new(config);
+    /// Baixa o vídeo de origem, registrando o progresso a cada 10%
+    pub async fn download(&self, bucket_name: &str) -> anyhow::Result<FileCheckpoint> {
+        let mut last_logged = 0;
+
+        self.download_with_progress(bucket_name, |progress| {
+            let percent = progress.percent();
+
+            if percent >= last_logged + 10 || progress.received == progress.total {
+                last_logged = percent;
+                tracing::info!(
+                    "Video {}: downloaded {} of {} bytes ({}%)",
+                    self.video.id,
+                    progress.received,
+                    progress.total,
+                    percent
+                );
+            }
+        })
+        .await
+    }
+
+    /// Baixa o vídeo em streaming, chunk a chunk, direto para o arquivo local,
+    /// calculando o SHA-256 do que foi gravado.
+    /// Antes de criar o arquivo confere se há espaço livre para todos os
+    /// estágios. Em caso de falha o arquivo parcial é removido
+    pub async fn download_with_progress<F>(
+        &self,
+        bucket_name: &str,
+        mut on_progress: F,
+    ) -> anyhow::Result<FileCheckpoint>
+    where
+        F: FnMut(DownloadProgress),
+    {
+        let file_path = self.workspace.source_path();
+
+        let result = self
+            .stream_to_file(bucket_name, &file_path, &mut on_progress)
+            .await;
 
-        let data = client
-            .download_object(
-                &GetObjectRequest {
-                    bucket: bucket_name.to_string(),
-                    object: self.video.file_path.clone(),
-                    ..Default::default()
-                },
-                &Range::default(),
-            )
+        let checkpoint = match result {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                if let Err(e) = tokio::fs::remove_file(&file_path).await
+                    && e.kind() != std::io::ErrorKind::NotFound
+                {
+                    tracing::warn!("Failed to remove partial file {:?}: {}", file_path, e);
+                }
+                return Err(e);
+            }
+        };
+
+        tracing::info!("Video {} has been stored at {:?}", self.video.id, file_path);
+
+        Ok(checkpoint)
+    }
+
+    async fn stream_to_file<F>(
+        &self,
+        bucket_name: &str,
+        file_path: &Path,
+        on_progress: &mut F,
+    ) -> anyhow::Result<FileCheckpoint>
+    where
+        F: FnMut(DownloadProgress),
+    {
+        let mut reader = self
+            .store
+            .get_stream(bucket_name, &self.video.file_path)
             .await?;
 
-        let local_storage_path =
-            env::var("localStoragePath").unwrap_or_else(|_| "/tmp".to_string());
+        self.workspace.prepare().await?;
+        self.workspace
+            .ensure_free_space(reader.size.saturating_mul(REQUIRED_SPACE_FACTOR))?;
+
+        let mut file = File::create(file_path).await?;
+        let mut hasher = Sha256::new();
+        let mut received = 0;
+
+        while let Some(chunk) = reader.stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            hasher.update(&chunk);
 
-        let file_path = PathBuf::from(local_storage_path).join(format!("{}.mp4", self.video.id));
+            received += chunk.len() as u64;
+            on_progress(DownloadProgress {
+                received,
+                total: reader.size,
+            });
+        }
 
-        let mut file = File::create(&file_path).await?;
-        file.write_all(&data).await?;
         file.flush().await?;
 
-        tracing::info!("Video {} has been stored at {:?}", self.video.id, file_path);
+        if received != reader.size {
+            anyhow::bail!(
+                "download of {} was truncated: received {} of {} bytes",
+                self.video.file_path,
+                received,
+                reader.size
+            );
+        }
 
-        Ok(())
+        Ok(FileCheckpoint {
+            size: received,
+            sha256: format!("{:x}", hasher.finalize()),
+            completed_at: Utc::now(),
+        })
     }
 
-    async fn fragment(&self) -> anyhow::Result<()> {
-        let local_storage_path =
-            env::var("localStoragePath").unwrap_or_else(|_| "/tmp".to_string());
+    pub async fn fragment(&self) -> anyhow::Result<FileCheckpoint> {
+        tokio::fs::create_dir_all(self.workspace.output_dir()).await?;
 
-        tokio::fs::create_dir_all(format!("{}/{}", local_storage_path, self.video.id))
-            .await
-            .expect("Failed to create tmp directory");
+        let source = self.workspace.source_path().to_string_lossy().to_string();
+        let destination = self.workspace.fragment_path();
 
-        let source = format!("{}/{}.mp4", local_storage_path, self.video.id);
-        let destination = format!("{}/{}.frag", local_storage_path, self.video.id);
+        Self::run_tool(
+            "mp4fragment",
+            &[source, destination.to_string_lossy().to_string()],
+        )
+        .await?;
 
-        let output = tokio::process::Command::new("mp4fragment")
-            .args(&[source, destination])
-            .output()
-            .await?;
+        file_checkpoint(&destination).await
+    }
 
-        Self::print_output(&output);
+    pub async fn encode(&self) -> anyhow::Result<EncodeCheckpoint> {
+        let cmd_args = vec![
+            self.workspace.fragment_path().to_string_lossy().to_string(),
+            "--use-segment-timeline".to_string(),
+            "-o".to_string(),
+            self.workspace.output_dir().to_string_lossy().to_string(),
+            "-f".to_string(),
+            "--exec-dir".to_string(),
+            "/opt/bento4/bin/".to_string(),
+        ];
+
+        Self::run_tool("mp4dash", &cmd_args).await?;
+
+        let files = self
+            .output_files()
+            .await?
+            .into_iter()
+            .map(|(_, file)| file)
+            .collect();
+
+        Ok(EncodeCheckpoint {
+            files,
+            completed_at: Utc::now(),
+        })
+    }
 
-        Ok(())
+    /// O vídeo de origem baixado em uma tentativa anterior continua íntegro
+    pub async fn has_source(&self, checkpoint: &FileCheckpoint) -> bool {
+        matches_checkpoint(&self.workspace.source_path(), checkpoint).await
     }
 
-    async fn encode(&self) -> anyhow::Result<()> {
-        let mut cmd_args = vec![];
+    /// O arquivo fragmentado em uma tentativa anterior continua íntegro
+    pub async fn has_fragment(&self, checkpoint: &FileCheckpoint) -> bool {
+        matches_checkpoint(&self.workspace.fragment_path(), checkpoint).await
+    }
 
-        let local_storage_path =
-            env::var("localStoragePath").unwrap_or_else(|_| "/tmp".to_string());
+    /// Todos os arquivos do encode anterior continuam no diretório de saída
+    /// com o mesmo tamanho
+    pub async fn has_encoded_output(&self, checkpoint: &EncodeCheckpoint) -> bool {
+        match self.output_files().await {
+            Ok(files) => checkpoint
+                .files
+                .iter()
+                .all(|expected| files.iter().any(|(_, file)| file == expected)),
+            Err(e) => {
+                tracing::debug!("Encoded output of video {} is gone: {}", self.video.id, e);
+                false
+            }
+        }
+    }
 
-        cmd_args.push(format!("{}/{}.frag", local_storage_path, self.video.id));
-        cmd_args.push("--use-segment-timeline".to_string());
-        cmd_args.push("-o".to_string());
-        cmd_args.push(format!("{}/{}", local_storage_path, self.video.id));
-        cmd_args.push("-f".to_string());
-        cmd_args.push("--exec-dir".to_string());
-        cmd_args.push("/opt/bento4/bin/".to_string());
+    /// Envia para o bucket de saída os arquivos gerados pelo mp4dash que ainda
+    /// não constam em `checkpoints.uploaded`, usando o caminho relativo ao
+    /// workspace como nome do objeto. Cada objeto confirmado é
+    /// registrado nos checkpoints, mesmo que outros falhem
+    pub async fn upload(
+        &self,
+        bucket_name: &str,
+        checkpoints: &mut JobCheckpoints,
+    ) -> anyhow::Result<()> {
+        let (pending, skipped): (Vec<_>, Vec<_>) = self
+            .output_files()
+            .await?
+            .into_iter()
+            .partition(|(_, file)| !checkpoints.is_uploaded(file));
+        let pending = pending.into_iter().map(|(path, _)| path).collect();
+
+        let manager = UploadManager::new(
+            self.store.clone(),
+            bucket_name.to_string(),
+            self.upload_concurrency,
+        );
+        let report = manager.upload_files(self.workspace.dir(), pending).await;
 
-        let output = tokio::process::Command::new("mp4dash")
-            .args(&cmd_args)
-            .output()
-            .await?;
+        for object in &report.uploaded {
+            checkpoints.record_upload(OutputFile {
+                name: object.name.clone(),
+                size: object.size,
+            });
+        }
 
-        Self::print_output(&output);
+        if !report.is_complete() {
+            let failed: Vec<_> = report
+                .failures
+                .iter()
+                .map(|f| format!("{} ({})", f.object, f.error))
+                .collect();
+
+            anyhow::bail!(
+                "{} of {} files failed to upload: {}",
+                report.failures.len(),
+                report.total(),
+                failed.join("; ")
+            );
+        }
+
+        tracing::info!(
+            "Uploaded {} files for video {} to bucket {} ({} already uploaded)",
+            report.uploaded.len(),
+            self.video.id,
+            bucket_name,
+            skipped.len()
+        );
 
         Ok(())
     }
 
-    async fn finish(&self) -> anyhow::Result<()> {
-        let local_storage_path =
-            env::var("localStoragePath").unwrap_or_else(|_| "/tmp".to_string());
+    /// Remove o workspace do job. A limpeza é best-effort: uma falha só é
+    /// registrada e não impede a conclusão do job
+    pub async fn finish(&self) -> anyhow::Result<()> {
+        self.workspace.cleanup().await;
 
-        tokio::fs::remove_file(format!("{}/{}.mp4", local_storage_path, self.video.id))
-            .await
-            .expect("Failed to remove mp4 file");
+        tracing::info!("Cleaned up files for video {}", self.video.id);
 
-        tokio::fs::remove_file(format!("{}/{}.frag", local_storage_path, self.video.id))
-            .await
-            .expect("Failed to remove fragment file");
+        Ok(())
+    }
+
+    /// Arquivos do diretório de saída com o nome do objeto correspondente no bucket
+    async fn output_files(&self) -> std::io::Result<Vec<(PathBuf, OutputFile)>> {
+        let mut files = Vec::new();
+
+        for path in collect_files(&self.workspace.output_dir()).await? {
+            let size = tokio::fs::metadata(&path).await?.len();
+            let name = object_name(self.workspace.dir(), &path);
+            files.push((path, OutputFile { name, size }));
+        }
 
-        tokio::fs::remove_dir_all(format!("{}/{}", local_storage_path, self.video.id))
+        Ok(files)
+    }
+
+    /// Executa a ferramenta e retorna um `EncodingError` se ela não puder ser
+    /// iniciada ou terminar com status diferente de zero
+    async fn run_tool(tool: &str, args: &[String]) -> Result<(), EncodingError> {
+        let output = tokio::process::Command::new(tool)
+            .args(args)
+            .output()
             .await
-            .expect("Failed to remove video directory");
+            .map_err(|source| EncodingError::Spawn {
+                tool: tool.to_string(),
+                args: args.to_vec(),
+                source,
+            })?;
 
-        tracing::info!("Cleaned up files for video {}", self.video.id);
+        Self::print_output(&output);
 
-        Ok(())
+        match EncodingError::from_output(tool, args, &output) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
 
     fn print_output(output: &std::process::Output) {
@@ -130,43 +345,299 @@ where
     }
 }
 
+/// Tamanho e SHA-256 de um arquivo local, lido em blocos
+async fn file_checkpoint(path: &Path) -> anyhow::Result<FileCheckpoint> {
+    let mut file = File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut size = 0;
+
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+
+    Ok(FileCheckpoint {
+        size,
+        sha256: format!("{:x}", hasher.finalize()),
+        completed_at: Utc::now(),
+    })
+}
+
+/// O arquivo existe com o mesmo tamanho e o mesmo SHA-256 do checkpoint.
+/// O tamanho é conferido antes para não ler arquivos obviamente diferentes
+async fn matches_checkpoint(path: &Path, checkpoint: &FileCheckpoint) -> bool {
+    match tokio::fs::metadata(path).await {
+        Ok(metadata) if metadata.len() == checkpoint.size => {}
+        _ => return false,
+    }
+
+    match file_checkpoint(path).await {
+        Ok(current) => current.sha256 == checkpoint.sha256,
+        Err(e) => {
+            tracing::debug!("Failed to verify {:?}: {}", path, e);
+            false
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{application::VideoRepository, domain::Video, framework::Database};
-    use sqlx::Sqlite;
+    use crate::{
+        application::{InMemoryDatabase, InMemoryVideoRepository, WorkspaceManager},
+        domain::Video,
+        framework::{
+            ByteStream, GcsObjectStore, LocalObjectStore, ObjectReader, ObjectStoreError,
+            StoredObject,
+        },
+    };
     use std::env;
 
-    async fn setup_test_db() -> Database<Sqlite> {
-        let database_url =
-            env::var("DATABASE_URL_TEST").unwrap_or_else(|_| "sqlite::memory:".to_string());
+    /// Workspace de um job novo no diretório temporário, removido no drop
+    fn workspace(video: &Video) -> Workspace {
+        WorkspaceManager::new(env::temp_dir()).workspace(uuid::Uuid::new_v4(), video.id)
+    }
+
+    #[tokio::test]
+    async fn test_video_service_run_tool_reports_exit_code_and_stderr() {
+        let args = vec![
+            "-c".to_string(),
+            "echo starting; echo 'invalid input file' >&2; exit 3".to_string(),
+        ];
+
+        let error =
+            VideoService::<InMemoryVideoRepository, LocalObjectStore>::run_tool("sh", &args)
+                .await
+                .expect_err("Tool should fail");
+
+        match &error {
+            EncodingError::Exit {
+                tool,
+                exit_code,
+                stderr,
+                ..
+            } => {
+                assert_eq!(tool, "sh");
+                assert_eq!(*exit_code, Some(3));
+                assert_eq!(stderr, "invalid input file");
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+        assert!(error.to_string().starts_with("sh exited with code 3"));
+
+        let missing = VideoService::<InMemoryVideoRepository, LocalObjectStore>::run_tool(
+            "mp4-missing-tool",
+            &[],
+        )
+        .await
+        .expect_err("Missing tool should fail");
+        assert!(matches!(missing, EncodingError::Spawn { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_video_service_download_and_upload_with_local_store() {
+        let db = InMemoryDatabase::new();
+        let root = env::temp_dir().join(format!("store-{}", uuid::Uuid::new_v4()));
+        let store = LocalObjectStore::new(&root);
+
+        store
+            .put("input", "videos/source.mp4", b"fake mp4".to_vec())
+            .await
+            .expect("Failed to put source video");
+
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        let workspace = workspace(&video);
+        let video_service = VideoService::new(db.videos(), video, store.clone(), workspace);
 
-        Database::<Sqlite>::new(database_url, Some(true))
+        let checkpoint = video_service
+            .download("input")
             .await
-            .expect("Failed to create test database connection")
+            .expect("Failed to download video");
+        assert_eq!(checkpoint.size, 8);
+        assert!(video_service.has_source(&checkpoint).await);
+
+        let video_id = video_service.video.id.to_string();
+        let source = video_service.workspace.source_path();
+        let output_dir = video_service.workspace.output_dir();
+
+        assert_eq!(tokio::fs::read(&source).await.unwrap(), b"fake mp4");
+
+        // Um arquivo alterado com o mesmo tamanho não passa na verificação
+        tokio::fs::write(&source, b"fake mp5").await.unwrap();
+        assert!(!video_service.has_source(&checkpoint).await);
+
+        tokio::fs::create_dir_all(&output_dir).await.unwrap();
+        tokio::fs::write(output_dir.join("stream.mpd"), b"<MPD/>")
+            .await
+            .unwrap();
+
+        let mut checkpoints = JobCheckpoints::default();
+        video_service
+            .upload("output", &mut checkpoints)
+            .await
+            .expect("Failed to upload video");
+
+        let uploaded = store.list("output", &video_id).await.unwrap();
+        assert_eq!(uploaded.len(), 1);
+        assert_eq!(uploaded[0].name, format!("{}/stream.mpd", video_id));
+        assert_eq!(checkpoints.uploaded.len(), 1);
+        assert_eq!(checkpoints.uploaded[0].name, uploaded[0].name);
+
+        // Uma nova tentativa envia só os arquivos que ainda não constam no manifesto
+        tokio::fs::write(output_dir.join("seg-1.m4s"), b"segment")
+            .await
+            .unwrap();
+        store.delete("output", &uploaded[0].name).await.unwrap();
+
+        video_service
+            .upload("output", &mut checkpoints)
+            .await
+            .expect("Failed to upload video");
+
+        let uploaded: Vec<_> = store
+            .list("output", &video_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|o| o.name)
+            .collect();
+        assert_eq!(uploaded, vec![format!("{}/seg-1.m4s", video_id)]);
+        assert_eq!(checkpoints.uploaded.len(), 2);
+
+        // O fragmento nunca foi gerado: a limpeza não entra em pânico
+        video_service.finish().await.expect("Failed to finish");
+        assert!(!video_service.workspace.dir().exists());
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    /// Store que entrega um chunk e depois falha, simulando uma conexão interrompida
+    #[derive(Clone)]
+    struct BrokenStore;
+
+    impl ObjectStore for BrokenStore {
+        async fn get(&self, _bucket: &str, _object: &str) -> Result<Vec<u8>, ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+
+        async fn get_stream(
+            &self,
+            _bucket: &str,
+            _object: &str,
+        ) -> Result<ObjectReader, ObjectStoreError> {
+            let chunks = vec![
+                Ok(b"partial".to_vec()),
+                Err(ObjectStoreError::Backend("connection reset".to_string())),
+            ];
+            let stream: ByteStream = futures::stream::iter(chunks).boxed();
+
+            Ok(ObjectReader { size: 100, stream })
+        }
+
+        async fn put(
+            &self,
+            _bucket: &str,
+            _object: &str,
+            _data: Vec<u8>,
+        ) -> Result<StoredObject, ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+
+        async fn put_stream(
+            &self,
+            _bucket: &str,
+            _object: &str,
+            _reader: ObjectReader,
+        ) -> Result<StoredObject, ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+
+        async fn list(
+            &self,
+            _bucket: &str,
+            _prefix: &str,
+        ) -> Result<Vec<StoredObject>, ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+
+        async fn delete(&self, _bucket: &str, _object: &str) -> Result<(), ObjectStoreError> {
+            Err(ObjectStoreError::Backend("not supported".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_video_service_download_reports_progress() {
+        let db = InMemoryDatabase::new();
+        let root = env::temp_dir().join(format!("store-{}", uuid::Uuid::new_v4()));
+        let store = LocalObjectStore::new(&root);
+
+        // Maior que um chunk, para gerar mais de um evento de progresso
+        let content = vec![7u8; 200 * 1024];
+        store
+            .put("input", "videos/source.mp4", content.clone())
+            .await
+            .unwrap();
+
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        let workspace = workspace(&video);
+        let video_service = VideoService::new(db.videos(), video, store, workspace);
+
+        let mut events = Vec::new();
+        video_service
+            .download_with_progress("input", |progress| events.push(progress))
+            .await
+            .expect("Failed to download video");
+
+        assert!(events.len() > 1);
+        assert!(events.windows(2).all(|w| w[0].received < w[1].received));
+        assert_eq!(
+            events.last(),
+            Some(&DownloadProgress {
+                received: content.len() as u64,
+                total: content.len() as u64,
+            })
+        );
+
+        let source = video_service.workspace.source_path();
+        assert_eq!(tokio::fs::read(&source).await.unwrap(), content);
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_video_service_download_removes_partial_file_on_failure() {
+        let db = InMemoryDatabase::new();
+        let video = Video::new("resource_123".to_string(), "videos/source.mp4".to_string());
+        let workspace = workspace(&video);
+        let video_service = VideoService::new(db.videos(), video, BrokenStore, workspace);
+
+        let result = video_service.download("input").await;
+        assert!(result.is_err());
+        assert!(!video_service.workspace.source_path().exists());
     }
 
     #[tokio::test]
     #[ignore]
     async fn test_video_service_download() {
-        let db = setup_test_db().await;
-        let video_repository = VideoRepository::new(db);
+        let db = InMemoryDatabase::new();
+        let video_repository = db.videos();
 
         let video = Video::new(
             "3fa3291e-5daf-4386-9a67-69d19e1690c5".to_string(),
             "videos/3fa3291e-5daf-4386-9a67-69d19e1690c5/videos/3fa3291e-5daf-4386-9a67-69d19e1690c5-b8c187dd77c950e9b117bcc19e35a9005e45001593f7f4260040cee47d77faa0.mp4".to_string(),
         );
 
-        let video_service = VideoService::new(video_repository, video.clone());
-
-        let tmp_path = "./tmp";
-        tokio::fs::create_dir_all(tmp_path)
+        let store = GcsObjectStore::from_env()
             .await
-            .expect("Failed to create tmp directory");
-
-        unsafe {
-            env::set_var("localStoragePath", tmp_path);
-        }
+            .expect("Failed to create GCS client");
+        let workspace = WorkspaceManager::new("./tmp").workspace(uuid::Uuid::new_v4(), video.id);
+        let video_service = VideoService::new(video_repository, video.clone(), store, workspace);
 
         let result = video_service
             .download("micro-admin-typescript-josemoura212")
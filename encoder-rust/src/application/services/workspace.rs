@@ -0,0 +1,308 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use uuid::Uuid;
+
+use crate::config::StorageConfig;
+
+#[derive(Debug)]
+pub enum WorkspaceError {
+    /// Não há espaço livre suficiente no disco do workspace
+    InsufficientSpace {
+        path: PathBuf,
+        required: u64,
+        available: u64,
+    },
+    Io(std::io::Error),
+}
+
+impl fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceError::InsufficientSpace {
+                path,
+                required,
+                available,
+            } => write!(
+                f,
+                "not enough disk space at {:?}: {} bytes required, {} available",
+                path, required, available
+            ),
+            WorkspaceError::Io(e) => write!(f, "workspace io error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceError {}
+
+impl From<std::io::Error> for WorkspaceError {
+    fn from(e: std::io::Error) -> Self {
+        WorkspaceError::Io(e)
+    }
+}
+
+/// Cria os workspaces dos jobs dentro de `root` (o `localStoragePath`)
+#[derive(Debug, Clone)]
+pub struct WorkspaceManager {
+    pub root: PathBuf,
+    /// Mantém os arquivos de todos os jobs no disco, para depuração
+    pub keep_artifacts: bool,
+}
+
+impl WorkspaceManager {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        WorkspaceManager {
+            root: root.into(),
+            keep_artifacts: false,
+        }
+    }
+
+    pub fn from_config(config: &StorageConfig) -> Self {
+        WorkspaceManager {
+            root: config.local_storage_path.clone(),
+            keep_artifacts: config.keep_artifacts,
+        }
+    }
+
+    /// Workspace do job. O diretório só é criado em `Workspace::prepare`
+    pub fn workspace(&self, job_id: Uuid, video_id: Uuid) -> Workspace {
+        Workspace {
+            dir: self.root.join(job_id.to_string()),
+            video_id,
+            keep: self.keep_artifacts,
+            cleaned: AtomicBool::new(false),
+        }
+    }
+
+    /// Remove o diretório de um job que não está mais em execução (ex.: na
+    /// recuperação após um crash), ignorando se ele não existir
+    pub async fn remove(&self, job_id: Uuid) -> std::io::Result<()> {
+        if self.keep_artifacts {
+            return Ok(());
+        }
+
+        match tokio::fs::remove_dir_all(self.root.join(job_id.to_string())).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Diretório de um job com os arquivos de cada estágio:
+/// `{video_id}.mp4` (origem), `{video_id}.frag` (fragmentado) e `{video_id}/`
+/// (saída do mp4dash, enviada ao bucket com os nomes relativos ao workspace).
+///
+/// O diretório deve ser removido com `cleanup` ao fim da tentativa. Um
+/// workspace descartado sem `cleanup` (ex.: erro ao gravar o job) é removido
+/// em segundo plano, sem bloquear o runtime, a menos que `keep` tenha sido
+/// chamado ou o gerenciador mantenha os artefatos. Falhas na remoção são
+/// apenas registradas
+#[derive(Debug)]
+pub struct Workspace {
+    dir: PathBuf,
+    video_id: Uuid,
+    keep: bool,
+    cleaned: AtomicBool,
+}
+
+impl Workspace {
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn source_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.mp4", self.video_id))
+    }
+
+    pub fn fragment_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.frag", self.video_id))
+    }
+
+    pub fn output_dir(&self) -> PathBuf {
+        self.dir.join(self.video_id.to_string())
+    }
+
+    /// Cria o diretório do workspace, se ainda não existir
+    pub async fn prepare(&self) -> std::io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await
+    }
+
+    /// Falha se o disco do workspace tiver menos de `required` bytes livres
+    pub fn ensure_free_space(&self, required: u64) -> Result<(), WorkspaceError> {
+        let Some(available) = available_space(&self.dir)? else {
+            return Ok(());
+        };
+
+        if available < required {
+            return Err(WorkspaceError::InsufficientSpace {
+                path: self.dir.clone(),
+                required,
+                available,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Mantém os arquivos ao descartar o workspace, para que a próxima
+    /// tentativa do job reaproveite os estágios concluídos
+    pub fn keep(&mut self) {
+        self.keep = true;
+    }
+
+    pub fn is_kept(&self) -> bool {
+        self.keep
+    }
+
+    /// Remove o diretório agora, a menos que os arquivos devam ser mantidos
+    pub async fn cleanup(&self) {
+        if self.keep {
+            tracing::info!("Keeping workspace {:?}", self.dir);
+            return;
+        }
+
+        self.cleaned.store(true, Ordering::Relaxed);
+        if let Err(e) = tokio::fs::remove_dir_all(&self.dir).await {
+            log_removal_error(&self.dir, e);
+        }
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        if self.keep || self.cleaned.load(Ordering::Relaxed) {
+            return;
+        }
+
+        // O drop pode acontecer numa thread do runtime: a remoção vai para o
+        // pool de bloqueio em vez de travar os demais jobs
+        let dir = std::mem::take(&mut self.dir);
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn_blocking(move || {
+                    if let Err(e) = std::fs::remove_dir_all(&dir) {
+                        log_removal_error(&dir, e);
+                    }
+                });
+            }
+            Err(_) => tracing::warn!("Workspace {:?} dropped without cleanup", dir),
+        }
+    }
+}
+
+fn log_removal_error(dir: &Path, e: std::io::Error) {
+    if e.kind() != std::io::ErrorKind::NotFound {
+        tracing::warn!("Failed to remove workspace {:?}: {}", dir, e);
+    }
+}
+
+/// Espaço livre, em bytes, no sistema de arquivos de `path`.
+/// Retorna `None` em plataformas sem statvfs
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn available_space(path: &Path) -> std::io::Result<Option<u64>> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes())?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+    // SAFETY: `path` é uma string C válida e `stat` é um buffer do tipo esperado
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(Some(stat.f_bavail as u64 * stat.f_frsize as u64))
+}
+
+#[cfg(not(unix))]
+fn available_space(_path: &Path) -> std::io::Result<Option<u64>> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_workspace_paths_and_cleanup() {
+        let root = env::temp_dir().join(format!("workspaces-{}", Uuid::new_v4()));
+        let manager = WorkspaceManager::new(&root);
+        let (job_id, video_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let workspace = manager.workspace(job_id, video_id);
+        assert_eq!(workspace.dir(), root.join(job_id.to_string()));
+        assert_eq!(
+            workspace.source_path(),
+            root.join(job_id.to_string())
+                .join(format!("{}.mp4", video_id))
+        );
+        assert_eq!(
+            workspace.output_dir(),
+            root.join(job_id.to_string()).join(video_id.to_string())
+        );
+
+        workspace.prepare().await.unwrap();
+        workspace.ensure_free_space(1).unwrap();
+        assert!(matches!(
+            workspace.ensure_free_space(u64::MAX),
+            Err(WorkspaceError::InsufficientSpace { .. })
+        ));
+
+        // O fragmento nunca foi gerado: a limpeza não pode entrar em pânico
+        tokio::fs::write(workspace.source_path(), b"mp4")
+            .await
+            .unwrap();
+        let dir = workspace.dir().to_path_buf();
+        workspace.cleanup().await;
+        assert!(!dir.exists());
+
+        // Workspaces mantidos sobrevivem à limpeza e ao drop e são reabertos
+        // pelo mesmo job
+        let mut workspace = manager.workspace(job_id, video_id);
+        workspace.prepare().await.unwrap();
+        workspace.keep();
+        workspace.cleanup().await;
+        drop(workspace);
+        assert!(dir.exists());
+
+        manager.remove(job_id).await.unwrap();
+        assert!(!dir.exists());
+        manager.remove(job_id).await.unwrap();
+
+        let manager = WorkspaceManager {
+            keep_artifacts: true,
+            ..manager
+        };
+        let workspace = manager.workspace(job_id, video_id);
+        workspace.prepare().await.unwrap();
+        workspace.cleanup().await;
+        drop(workspace);
+        assert!(dir.exists());
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_workspace_dropped_without_cleanup_is_removed_in_background() {
+        let root = env::temp_dir().join(format!("workspaces-{}", Uuid::new_v4()));
+        let workspace = WorkspaceManager::new(&root).workspace(Uuid::new_v4(), Uuid::new_v4());
+        workspace.prepare().await.unwrap();
+        let dir = workspace.dir().to_path_buf();
+        drop(workspace);
+
+        for _ in 0..100 {
+            if !dir.exists() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(!dir.exists());
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+}
@@ -0,0 +1,28 @@
+use std::{fmt, path::PathBuf};
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Não foi possível ler o arquivo .env ou o arquivo TOML
+    Source { path: PathBuf, message: String },
+    /// Todos os problemas encontrados na validação
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Source { path, message } => {
+                write!(f, "failed to read config file {:?}: {}", path, message)
+            }
+            ConfigError::Invalid(problems) => {
+                write!(f, "invalid configuration ({} problems):", problems.len())?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
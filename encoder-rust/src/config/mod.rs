@@ -0,0 +1,7 @@
+mod config_error;
+mod settings;
+mod sources;
+
+pub use config_error::ConfigError;
+pub use settings::{Config, DatabaseConfig, JobsConfig, StorageConfig};
+pub use sources::ConfigSources;
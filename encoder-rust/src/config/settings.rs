@@ -0,0 +1,492 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use url::Url;
+
+use crate::{
+    config::{ConfigError, ConfigSources},
+    domain::DEFAULT_MAX_ATTEMPTS,
+    framework::queue::RabbitMqConfig,
+};
+
+const DEFAULT_DATABASE_MAX_CONNECTIONS: u32 = 10;
+const DEFAULT_UPLOAD_CONCURRENCY: usize = 50;
+const DEFAULT_WORKERS: usize = 1;
+const DEFAULT_LEASE_SECS: u64 = 5 * 60;
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_RETRY_BASE_DELAY_SECS: u64 = 30;
+const DEFAULT_RETRY_MAX_DELAY_SECS: u64 = 30 * 60;
+const DEFAULT_RETRY_JITTER: f64 = 0.2;
+const DEFAULT_SCHEDULER_INTERVAL_SECS: u64 = 10;
+const DEFAULT_STALE_AFTER_SECS: u64 = 60 * 60;
+const DEFAULT_RECOVERY_INTERVAL_SECS: u64 = 60;
+
+// O serviço só roda sobre Postgres; o SQLite é usado apenas nos testes
+const DATABASE_SCHEMES: [&str; 2] = ["postgres", "postgresql"];
+
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub auto_migrate: bool,
+    pub max_connections: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageConfig {
+    /// Diretório dos workspaces dos jobs
+    pub local_storage_path: PathBuf,
+    /// Mantém os arquivos dos jobs no disco, para depuração
+    pub keep_artifacts: bool,
+    pub input_bucket_name: String,
+    pub output_bucket_name: String,
+    pub upload_concurrency: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct JobsConfig {
+    /// Quantidade de jobs processados ao mesmo tempo
+    pub workers: usize,
+    /// Tentativas de cada job novo, contando a primeira
+    pub max_attempts: u32,
+    pub lease: Duration,
+    /// Espera dos workers quando não há jobs pendentes
+    pub poll_interval: Duration,
+    /// Prazo para os jobs em andamento terminarem após SIGTERM/SIGINT
+    pub shutdown_timeout: Duration,
+    pub retry_base_delay: Duration,
+    pub retry_max_delay: Duration,
+    pub retry_jitter: f64,
+    /// Intervalo entre as execuções do RetryScheduler
+    pub scheduler_interval: Duration,
+    /// Tempo sem mudar de estágio para a recuperação considerar abandonado um
+    /// job ativo sem lease
+    pub stale_after: Duration,
+    /// Intervalo entre as execuções da recuperação de jobs abandonados
+    pub recovery_interval: Duration,
+}
+
+/// Configuração da aplicação, carregada e validada uma única vez na
+/// inicialização. Cada chave pode vir de uma variável de ambiente (os mesmos
+/// nomes do `.env.example`) ou do TOML:
+///
+/// ```toml
+/// [database]
+/// url = "postgres://postgres:postgres@db:5432/encoder"
+/// auto_migrate = true
+///
+/// [storage]
+/// local_path = "/tmp"
+/// input_bucket = "codeeducationtest"
+/// output_bucket = "codeeducationtest"
+///
+/// [rabbitmq]
+/// host = "rabbit"
+///
+/// [jobs]
+/// workers = 2
+/// ```
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub storage: StorageConfig,
+    pub rabbitmq: RabbitMqConfig,
+    pub jobs: JobsConfig,
+}
+
+impl Config {
+    /// Carrega a configuração do ambiente, do `.env` e do TOML
+    pub fn load() -> Result<Self, ConfigError> {
+        Self::from_sources(&ConfigSources::load()?)
+    }
+
+    /// Lê e valida todas as chaves, retornando todos os problemas de uma vez
+    pub fn from_sources(sources: &ConfigSources) -> Result<Self, ConfigError> {
+        let mut reader = Reader {
+            sources,
+            problems: Vec::new(),
+        };
+        let r = &mut reader;
+
+        let config = Config {
+            database: DatabaseConfig {
+                url: r.required("DATABASE_URL", "database.url"),
+                auto_migrate: r.flag("AUTO_MIGRATE_DB", "database.auto_migrate", false),
+                max_connections: r.parse(
+                    "DATABASE_MAX_CONNECTIONS",
+                    "database.max_connections",
+                    DEFAULT_DATABASE_MAX_CONNECTIONS,
+                ),
+            },
+            storage: StorageConfig {
+                local_storage_path: PathBuf::from(
+                    r.required("localStoragePath", "storage.local_path"),
+                ),
+                keep_artifacts: r.flag("KEEP_ARTIFACTS", "storage.keep_artifacts", false),
+                input_bucket_name: r.required("inputBucketName", "storage.input_bucket"),
+                output_bucket_name: r.required("outputBucketName", "storage.output_bucket"),
+                upload_concurrency: r.parse(
+                    "CONCURRENCY_UPLOAD",
+                    "storage.upload_concurrency",
+                    DEFAULT_UPLOAD_CONCURRENCY,
+                ),
+            },
+            rabbitmq: RabbitMqConfig {
+                user: r.string("RABBITMQ_DEFAULT_USER", "rabbitmq.user", "rabbitmq"),
+                password: r.string("RABBITMQ_DEFAULT_PASS", "rabbitmq.password", "rabbitmq"),
+                host: r.string("RABBITMQ_DEFAULT_HOST", "rabbitmq.host", "rabbit"),
+                port: r.parse("RABBITMQ_DEFAULT_PORT", "rabbitmq.port", 5672),
+                vhost: r.string("RABBITMQ_DEFAULT_VHOST", "rabbitmq.vhost", "/"),
+                consumer_name: r.string(
+                    "RABBITMQ_CONSUMER_NAME",
+                    "rabbitmq.consumer_name",
+                    "encoder",
+                ),
+                consumer_queue_name: r.string(
+                    "RABBITMQ_CONSUMER_QUEUE_NAME",
+                    "rabbitmq.consumer_queue_name",
+                    "videos",
+                ),
+                notification_exchange: r.string(
+                    "RABBITMQ_NOTIFICATION_EX",
+                    "rabbitmq.notification_exchange",
+                    "amq.direct",
+                ),
+                notification_routing_key: r.string(
+                    "RABBITMQ_NOTIFICATION_ROUTING_KEY",
+                    "rabbitmq.notification_routing_key",
+                    "jobs",
+                ),
+                dead_letter_exchange: r.string(
+                    "RABBITMQ_DLX",
+                    "rabbitmq.dead_letter_exchange",
+                    "dlx",
+                ),
+                dead_letter_queue: r.string(
+                    "RABBITMQ_DLQ",
+                    "rabbitmq.dead_letter_queue",
+                    "videos.dlq",
+                ),
+            },
+            jobs: JobsConfig {
+                workers: r.parse("JOB_WORKERS", "jobs.workers", DEFAULT_WORKERS),
+                max_attempts: r.parse(
+                    "JOB_MAX_ATTEMPTS",
+                    "jobs.max_attempts",
+                    DEFAULT_MAX_ATTEMPTS.unsigned_abs(),
+                ),
+                lease: r.seconds("JOB_LEASE_SECS", "jobs.lease_secs", DEFAULT_LEASE_SECS),
+                poll_interval: r.seconds(
+                    "JOB_POLL_INTERVAL_SECS",
+                    "jobs.poll_interval_secs",
+                    DEFAULT_POLL_INTERVAL_SECS,
+                ),
+                shutdown_timeout: r.seconds(
+                    "SHUTDOWN_TIMEOUT_SECS",
+                    "jobs.shutdown_timeout_secs",
+                    DEFAULT_SHUTDOWN_TIMEOUT_SECS,
+                ),
+                retry_base_delay: r.seconds(
+                    "RETRY_BASE_DELAY_SECS",
+                    "jobs.retry_base_delay_secs",
+                    DEFAULT_RETRY_BASE_DELAY_SECS,
+                ),
+                retry_max_delay: r.seconds(
+                    "RETRY_MAX_DELAY_SECS",
+                    "jobs.retry_max_delay_secs",
+                    DEFAULT_RETRY_MAX_DELAY_SECS,
+                ),
+                retry_jitter: r.parse("RETRY_JITTER", "jobs.retry_jitter", DEFAULT_RETRY_JITTER),
+                scheduler_interval: r.seconds(
+                    "RETRY_SCHEDULER_INTERVAL_SECS",
+                    "jobs.scheduler_interval_secs",
+                    DEFAULT_SCHEDULER_INTERVAL_SECS,
+                ),
+                stale_after: r.seconds(
+                    "RECOVERY_STALE_AFTER_SECS",
+                    "jobs.stale_after_secs",
+                    DEFAULT_STALE_AFTER_SECS,
+                ),
+                recovery_interval: r.seconds(
+                    "RECOVERY_INTERVAL_SECS",
+                    "jobs.recovery_interval_secs",
+                    DEFAULT_RECOVERY_INTERVAL_SECS,
+                ),
+            },
+        };
+
+        config.validate(&mut reader.problems);
+
+        if reader.problems.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError::Invalid(reader.problems))
+        }
+    }
+
+    /// Regras entre valores já lidos. Chaves obrigatórias ausentes já foram
+    /// reportadas e não são validadas de novo
+    fn validate(&self, problems: &mut Vec<String>) {
+        let database_url = &self.database.url;
+        if !database_url.is_empty() {
+            match Url::parse(database_url) {
+                Ok(url) if DATABASE_SCHEMES.contains(&url.scheme()) => {}
+                Ok(url) => problems.push(format!(
+                    "DATABASE_URL (database.url): unsupported scheme {:?}, expected one of {:?}",
+                    url.scheme(),
+                    DATABASE_SCHEMES
+                )),
+                Err(e) => problems.push(format!("DATABASE_URL (database.url): invalid url: {}", e)),
+            }
+        }
+
+        if let Err(e) = Url::parse(&self.rabbitmq.uri()) {
+            problems.push(format!(
+                "RABBITMQ_DEFAULT_HOST (rabbitmq.host): invalid AMQP url: {}",
+                e
+            ));
+        }
+
+        let local_storage_path = &self.storage.local_storage_path;
+        if !local_storage_path.as_os_str().is_empty()
+            && let Err(e) = ensure_writable(local_storage_path)
+        {
+            problems.push(format!(
+                "localStoragePath (storage.local_path): {:?} is not writable: {}",
+                local_storage_path, e
+            ));
+        }
+
+        let positive = [
+            (
+                "DATABASE_MAX_CONNECTIONS (database.max_connections)",
+                self.database.max_connections as u64,
+            ),
+            (
+                "CONCURRENCY_UPLOAD (storage.upload_concurrency)",
+                self.storage.upload_concurrency as u64,
+            ),
+            ("JOB_WORKERS (jobs.workers)", self.jobs.workers as u64),
+            (
+                "JOB_MAX_ATTEMPTS (jobs.max_attempts)",
+                self.jobs.max_attempts as u64,
+            ),
+            (
+                "JOB_LEASE_SECS (jobs.lease_secs)",
+                self.jobs.lease.as_secs(),
+            ),
+            (
+                "JOB_POLL_INTERVAL_SECS (jobs.poll_interval_secs)",
+                self.jobs.poll_interval.as_secs(),
+            ),
+            (
+                "RETRY_SCHEDULER_INTERVAL_SECS (jobs.scheduler_interval_secs)",
+                self.jobs.scheduler_interval.as_secs(),
+            ),
+            (
+                "RECOVERY_INTERVAL_SECS (jobs.recovery_interval_secs)",
+                self.jobs.recovery_interval.as_secs(),
+            ),
+        ];
+        for (key, value) in positive {
+            if value == 0 {
+                problems.push(format!("{}: must be greater than zero", key));
+            }
+        }
+
+        if self.jobs.retry_base_delay > self.jobs.retry_max_delay {
+            problems.push(
+                "RETRY_BASE_DELAY_SECS (jobs.retry_base_delay_secs): must not exceed RETRY_MAX_DELAY_SECS"
+                    .to_string(),
+            );
+        }
+
+        if !(0.0..=1.0).contains(&self.jobs.retry_jitter) {
+            problems.push("RETRY_JITTER (jobs.retry_jitter): must be between 0 and 1".to_string());
+        }
+    }
+}
+
+/// Cria o diretório, se necessário, e confere que é possível gravar nele
+fn ensure_writable(path: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(path)?;
+
+    let probe = path.join(format!(".write-test-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(&probe)
+}
+
+/// Lê as chaves das fontes acumulando os problemas encontrados
+struct Reader<'a> {
+    sources: &'a ConfigSources,
+    problems: Vec<String>,
+}
+
+impl<'a> Reader<'a> {
+    /// Valor da chave; vazio conta como ausente
+    fn value(&self, var: &str, path: &str) -> Option<&'a str> {
+        self.sources
+            .get(var, path)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn required(&mut self, var: &str, path: &str) -> String {
+        match self.value(var, path) {
+            Some(value) => value.to_string(),
+            None => {
+                self.problems
+                    .push(format!("{} ({}) is required", var, path));
+                String::new()
+            }
+        }
+    }
+
+    fn string(&self, var: &str, path: &str, default: &str) -> String {
+        self.value(var, path).unwrap_or(default).to_string()
+    }
+
+    fn parse<T>(&mut self, var: &str, path: &str, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let Some(raw) = self.value(var, path) else {
+            return default;
+        };
+
+        raw.parse().unwrap_or_else(|e| {
+            self.problems.push(format!(
+                "{} ({}): invalid value {:?}: {}",
+                var, path, raw, e
+            ));
+            default
+        })
+    }
+
+    fn flag(&mut self, var: &str, path: &str, default: bool) -> bool {
+        match self.value(var, path).map(str::to_lowercase).as_deref() {
+            None => default,
+            Some("true" | "1" | "yes") => true,
+            Some("false" | "0" | "no") => false,
+            Some(raw) => {
+                self.problems.push(format!(
+                    "{} ({}): invalid value {:?}: expected true or false",
+                    var, path, raw
+                ));
+                default
+            }
+        }
+    }
+
+    fn seconds(&mut self, var: &str, path: &str, default: u64) -> Duration {
+        Duration::from_secs(self.parse(var, path, default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn valid_sources(local_storage_path: &Path) -> ConfigSources {
+        ConfigSources::default()
+            .with_var(
+                "DATABASE_URL",
+                "postgres://postgres:postgres@db:5432/encoder",
+            )
+            .with_var("localStoragePath", &local_storage_path.to_string_lossy())
+            .with_var("inputBucketName", "input")
+            .with_var("outputBucketName", "output")
+    }
+
+    #[test]
+    fn test_config_loads_typed_values_with_defaults() {
+        let root = env::temp_dir().join(format!("config-{}", uuid::Uuid::new_v4()));
+        let sources = valid_sources(&root)
+            .with_var("AUTO_MIGRATE_DB", "true")
+            .with_toml("[jobs]\nworkers = 4\nstale_after_secs = 120")
+            .unwrap();
+
+        let config = Config::from_sources(&sources).expect("Config should be valid");
+
+        assert!(config.database.auto_migrate);
+        assert_eq!(config.storage.local_storage_path, root);
+        assert_eq!(
+            config.storage.upload_concurrency,
+            DEFAULT_UPLOAD_CONCURRENCY
+        );
+        assert_eq!(config.rabbitmq.port, 5672);
+        assert_eq!(config.rabbitmq.dead_letter_queue, "videos.dlq");
+        assert_eq!(config.jobs.workers, 4);
+        assert_eq!(config.jobs.max_attempts, 3);
+        assert_eq!(config.jobs.stale_after, Duration::from_secs(120));
+        assert_eq!(
+            config.jobs.recovery_interval,
+            Duration::from_secs(DEFAULT_RECOVERY_INTERVAL_SECS)
+        );
+
+        // O diretório é criado na validação
+        assert!(root.is_dir());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_config_reports_every_problem_at_once() {
+        let not_a_dir = env::temp_dir().join(format!("config-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&not_a_dir, b"").unwrap();
+
+        let sources = ConfigSources::default()
+            .with_var("DATABASE_URL", "mysql://db/encoder")
+            .with_var("localStoragePath", &not_a_dir.to_string_lossy())
+            .with_var("RABBITMQ_DEFAULT_PORT", "amqp")
+            .with_var("AUTO_MIGRATE_DB", "sometimes")
+            .with_var("JOB_WORKERS", "0");
+
+        let problems = match Config::from_sources(&sources) {
+            Err(ConfigError::Invalid(problems)) => problems,
+            other => panic!("unexpected result {:?}", other),
+        };
+
+        let expected = [
+            "AUTO_MIGRATE_DB",
+            "inputBucketName (storage.input_bucket) is required",
+            "outputBucketName (storage.output_bucket) is required",
+            "RABBITMQ_DEFAULT_PORT",
+            "unsupported scheme \"mysql\"",
+            "is not writable",
+            "JOB_WORKERS (jobs.workers): must be greater than zero",
+        ];
+        assert_eq!(problems.len(), expected.len(), "{:?}", problems);
+        for expected in expected {
+            assert!(
+                problems.iter().any(|p| p.contains(expected)),
+                "missing {:?} in {:?}",
+                expected,
+                problems
+            );
+        }
+
+        std::fs::remove_file(&not_a_dir).unwrap();
+    }
+
+    #[test]
+    fn test_config_rejects_sqlite_database() {
+        let root = env::temp_dir().join(format!("config-{}", uuid::Uuid::new_v4()));
+        let sources = valid_sources(&root).with_var("DATABASE_URL", "sqlite://encoder.db");
+
+        let problems = match Config::from_sources(&sources) {
+            Err(ConfigError::Invalid(problems)) => problems,
+            other => panic!("unexpected result {:?}", other),
+        };
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].contains("unsupported scheme \"sqlite\""));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
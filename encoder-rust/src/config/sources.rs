@@ -0,0 +1,180 @@
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+};
+
+use crate::config::ConfigError;
+
+const DOTENV_FILE: &str = ".env";
+const DEFAULT_CONFIG_FILE: &str = "encoder.toml";
+// Caminho de um arquivo TOML explícito; se informado, ele precisa existir
+const CONFIG_FILE_VAR: &str = "CONFIG_FILE";
+
+/// Valores brutos da configuração, por ordem de prioridade: variáveis de
+/// ambiente, arquivo `.env` e arquivo TOML. No TOML as chaves são caminhos
+/// com pontos (`[database] url = ...` vira `database.url`)
+#[derive(Debug, Default, Clone)]
+pub struct ConfigSources {
+    env: HashMap<String, String>,
+    dotenv: HashMap<String, String>,
+    file: HashMap<String, String>,
+}
+
+impl ConfigSources {
+    /// Lê o ambiente do processo, o `.env` do diretório atual (opcional) e o
+    /// TOML de `CONFIG_FILE` ou, se ele não for informado, o `encoder.toml`
+    /// do diretório atual (opcional)
+    pub fn load() -> Result<Self, ConfigError> {
+        let env: HashMap<_, _> = env::vars().collect();
+        let dotenv = read_dotenv(Path::new(DOTENV_FILE))?;
+
+        let file = match env.get(CONFIG_FILE_VAR).or(dotenv.get(CONFIG_FILE_VAR)) {
+            Some(path) => read_toml(Path::new(path))?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                read_toml(Path::new(DEFAULT_CONFIG_FILE))?
+            }
+            None => HashMap::new(),
+        };
+
+        Ok(ConfigSources { env, dotenv, file })
+    }
+
+    /// Define uma variável de ambiente, sem alterar o ambiente do processo
+    pub fn with_var(mut self, key: &str, value: &str) -> Self {
+        self.env.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Adiciona as chaves de um conteúdo TOML
+    pub fn with_toml(mut self, contents: &str) -> Result<Self, ConfigError> {
+        self.file
+            .extend(parse_toml(Path::new("<inline>"), contents)?);
+        Ok(self)
+    }
+
+    /// Valor de uma chave pelo nome da variável de ambiente ou, no TOML, pelo
+    /// caminho com pontos
+    pub fn get(&self, var: &str, path: &str) -> Option<&str> {
+        self.env
+            .get(var)
+            .or_else(|| self.dotenv.get(var))
+            .or_else(|| self.file.get(path))
+            .map(String::as_str)
+    }
+}
+
+fn read_dotenv(path: &Path) -> Result<HashMap<String, String>, ConfigError> {
+    let source_error = |e: dotenvy::Error| ConfigError::Source {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    };
+
+    let iter = match dotenvy::from_path_iter(path) {
+        Ok(iter) => iter,
+        Err(dotenvy::Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(HashMap::new());
+        }
+        Err(e) => return Err(source_error(e)),
+    };
+
+    iter.map(|item| item.map_err(source_error)).collect()
+}
+
+fn read_toml(path: &Path) -> Result<HashMap<String, String>, ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Source {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+
+    parse_toml(path, &contents)
+}
+
+fn parse_toml(path: &Path, contents: &str) -> Result<HashMap<String, String>, ConfigError> {
+    let table = contents
+        .parse::<toml::Table>()
+        .map_err(|e| ConfigError::Source {
+            path: PathBuf::from(path),
+            message: e.to_string(),
+        })?;
+
+    let mut values = HashMap::new();
+    flatten("", &table, &mut values);
+
+    Ok(values)
+}
+
+/// Converte as tabelas aninhadas em chaves com pontos. Valores que não são
+/// strings ficam no formato TOML (`true`, `10`, `0.2`)
+fn flatten(prefix: &str, table: &toml::Table, values: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let path = match prefix {
+            "" => key.clone(),
+            prefix => format!("{}.{}", prefix, key),
+        };
+
+        match value {
+            toml::Value::Table(table) => flatten(&path, table, values),
+            toml::Value::String(value) => {
+                values.insert(path, value.clone());
+            }
+            value => {
+                values.insert(path, value.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_sources_precedence_and_toml_paths() {
+        let sources = ConfigSources {
+            dotenv: HashMap::from([
+                (
+                    "DATABASE_URL".to_string(),
+                    "postgres://dotenv/db".to_string(),
+                ),
+                ("inputBucketName".to_string(), "dotenv-input".to_string()),
+            ]),
+            ..Default::default()
+        }
+        .with_var("DATABASE_URL", "postgres://env/db")
+        .with_toml(
+            r#"
+            [database]
+            url = "postgres://toml/db"
+            auto_migrate = true
+
+            [storage]
+            input_bucket = "toml-input"
+            output_bucket = "toml-output"
+            "#,
+        )
+        .unwrap();
+
+        let get = |var, path| sources.get(var, path);
+        assert_eq!(
+            get("DATABASE_URL", "database.url"),
+            Some("postgres://env/db")
+        );
+        assert_eq!(
+            get("inputBucketName", "storage.input_bucket"),
+            Some("dotenv-input")
+        );
+        assert_eq!(
+            get("outputBucketName", "storage.output_bucket"),
+            Some("toml-output")
+        );
+        assert_eq!(
+            get("AUTO_MIGRATE_DB", "database.auto_migrate"),
+            Some("true")
+        );
+        assert_eq!(get("KEEP_ARTIFACTS", "storage.keep_artifacts"), None);
+
+        let result = ConfigSources::default().with_toml("[database\nurl = 1");
+        assert!(matches!(result, Err(ConfigError::Source { .. })));
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Arquivo local gerado por um estágio, com o tamanho e o SHA-256 usados
+/// para confirmar que ele ainda está íntegro antes de reaproveitá-lo
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FileCheckpoint {
+    pub size: u64,
+    pub sha256: String,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Arquivo de saída do mp4dash, identificado pelo nome do objeto no bucket
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OutputFile {
+    pub name: String,
+    pub size: u64,
+}
+
+/// Arquivos gerados pelo encode
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EncodeCheckpoint {
+    pub files: Vec<OutputFile>,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Estágios concluídos nas tentativas anteriores do job, para que uma nova
+/// tentativa retome do ponto em que parou.
+/// Refazer um estágio invalida os checkpoints dos estágios seguintes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct JobCheckpoints {
+    pub downloaded: Option<FileCheckpoint>,
+    pub fragmented: Option<FileCheckpoint>,
+    pub encoded: Option<EncodeCheckpoint>,
+    /// Objetos já confirmados no bucket de saída
+    pub uploaded: Vec<OutputFile>,
+}
+
+impl JobCheckpoints {
+    pub fn record_download(&mut self, checkpoint: FileCheckpoint) {
+        self.downloaded = Some(checkpoint);
+        self.fragmented = None;
+        self.encoded = None;
+        self.uploaded.clear();
+    }
+
+    pub fn record_fragment(&mut self, checkpoint: FileCheckpoint) {
+        self.fragmented = Some(checkpoint);
+        self.encoded = None;
+        self.uploaded.clear();
+    }
+
+    pub fn record_encode(&mut self, checkpoint: EncodeCheckpoint) {
+        self.encoded = Some(checkpoint);
+        self.uploaded.clear();
+    }
+
+    /// Registra um objeto enviado, substituindo um registro anterior do mesmo objeto
+    pub fn record_upload(&mut self, file: OutputFile) {
+        self.uploaded.retain(|uploaded| uploaded.name != file.name);
+        self.uploaded.push(file);
+    }
+
+    /// O objeto já está no bucket com o mesmo tamanho do arquivo local
+    pub fn is_uploaded(&self, file: &OutputFile) -> bool {
+        self.uploaded.contains(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_checkpoint(sha256: &str) -> FileCheckpoint {
+        FileCheckpoint {
+            size: 10,
+            sha256: sha256.to_string(),
+            completed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_job_checkpoints_redoing_a_stage_invalidates_later_stages() {
+        let mut checkpoints = JobCheckpoints::default();
+        let segment = OutputFile {
+            name: "video-id/seg-1.m4s".to_string(),
+            size: 42,
+        };
+
+        checkpoints.record_download(file_checkpoint("source"));
+        checkpoints.record_fragment(file_checkpoint("fragment"));
+        checkpoints.record_encode(EncodeCheckpoint {
+            files: vec![segment.clone()],
+            completed_at: Utc::now(),
+        });
+        checkpoints.record_upload(segment.clone());
+        checkpoints.record_upload(segment.clone());
+
+        assert_eq!(checkpoints.uploaded.len(), 1);
+        assert!(checkpoints.is_uploaded(&segment));
+        assert!(!checkpoints.is_uploaded(&OutputFile {
+            size: 43,
+            ..segment
+        }));
+
+        checkpoints.record_fragment(file_checkpoint("fragment-2"));
+
+        assert!(checkpoints.downloaded.is_some());
+        assert_eq!(checkpoints.fragmented.unwrap().sha256, "fragment-2");
+        assert!(checkpoints.encoded.is_none());
+        assert!(checkpoints.uploaded.is_empty());
+    }
+
+    #[test]
+    fn test_job_checkpoints_deserialize_missing_fields() {
+        let checkpoints: JobCheckpoints = serde_json::from_str("{}").unwrap();
+        assert_eq!(checkpoints, JobCheckpoints::default());
+    }
+}
@@ -4,34 +4,416 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::Video;
+use crate::domain::{JobCheckpoints, JobEvent, JobStatus, JobStatusError, ValidationError, Video};
+
+/// Quantas tentativas um job recebe quando nenhum limite é informado
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 3;
+
+/// Erro registrado quando uma tentativa do job falha
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AttemptError {
+    pub attempt: i32,
+    /// Etapa em que a tentativa falhou
+    pub stage: JobStatus,
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+}
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Job {
     #[serde(rename = "job_id")]
     pub id: Uuid,
     pub output_bucket_path: String,
-    pub status: String,
+    pub status: JobStatus,
     pub video: Arc<Video>,
     #[serde(skip)]
     pub video_id: Uuid,
     pub error: Option<String>,
+    /// Worker que reivindicou o job e até quando a reivindicação é válida
+    #[serde(skip)]
+    pub worker_id: Option<String>,
+    #[serde(skip)]
+    pub lease_expires_at: Option<DateTime<Utc>>,
+    /// Versão da linha no banco quando o job foi carregado (controle de concorrência otimista)
+    #[serde(skip)]
+    pub version: i64,
+    /// Chave de idempotência da submissão (ver `Video::idempotency_key`).
+    /// Só um job não falho pode ter a mesma chave
+    #[serde(skip)]
+    pub idempotency_key: Option<String>,
+    /// Tentativas iniciadas e o limite antes de o job falhar de vez
+    #[serde(skip)]
+    pub attempts: i32,
+    #[serde(skip)]
+    pub max_attempts: i32,
+    /// A partir de quando um job em retrying pode voltar a pending
+    #[serde(skip)]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Erros de todas as tentativas que falharam, da primeira para a última
+    #[serde(skip)]
+    pub attempt_errors: Vec<AttemptError>,
+    /// Estágios concluídos que uma nova tentativa pode reaproveitar
+    #[serde(skip)]
+    pub checkpoints: JobCheckpoints,
+    /// Mensagem da fila que criou o job, se ele veio de uma
+    #[serde(skip)]
+    pub source_message: Option<String>,
+    /// Transições ainda não gravadas em job_events. Os repositórios as gravam
+    /// junto com o job e devolvem o job com a lista vazia
+    #[serde(skip)]
+    pub events: Vec<JobEvent>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl Job {
-    pub fn new(output_bucket_path: String, status: String, video: Arc<Video>) -> Job {
+    pub fn new(output_bucket_path: String, video: Arc<Video>) -> Job {
         let video_id = video.id;
+        let id = Uuid::new_v4();
+        let now = Utc::now();
         Job {
-            id: Uuid::new_v4(),
+            id,
             output_bucket_path,
-            status,
+            status: JobStatus::Pending,
             video,
             video_id,
             error: None,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            worker_id: None,
+            lease_expires_at: None,
+            version: 0,
+            idempotency_key: None,
+            attempts: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            next_attempt_at: None,
+            attempt_errors: Vec::new(),
+            checkpoints: JobCheckpoints::default(),
+            source_message: None,
+            events: vec![JobEvent::new(id, None, JobStatus::Pending, now)],
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Valida os campos obrigatórios e a consistência com o vídeo associado
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::new("job");
+
+        if self.id.is_nil() {
+            errors.add("id", "must be a valid uuid");
         }
+        errors.require_not_blank("output_bucket_path", &self.output_bucket_path);
+        if self.video_id.is_nil() {
+            errors.add("video_id", "must be a valid uuid");
+        } else if self.video_id != self.video.id {
+            errors.add("video_id", "must match the associated video");
+        }
+        if self.max_attempts < 1 {
+            errors.add("max_attempts", "must be at least 1");
+        }
+
+        errors.into_result()
+    }
+
+    /// Avança o job para o próximo status, rejeitando transições inválidas
+    pub fn transition_to(&mut self, next: JobStatus) -> Result<(), JobStatusError> {
+        self.transition_with(next, None)
+    }
+
+    /// Como `transition_to`, registrando no evento da transição a mensagem
+    /// e o tempo que o job passou no status anterior
+    fn transition_with(
+        &mut self,
+        next: JobStatus,
+        message: Option<String>,
+    ) -> Result<(), JobStatusError> {
+        if !self.status.can_transition_to(next) {
+            return Err(JobStatusError::InvalidTransition {
+                from: self.status,
+                to: next,
+            });
+        }
+
+        let now = Utc::now();
+        self.events.push(JobEvent {
+            worker_id: self.worker_id.clone(),
+            message,
+            ..JobEvent::new(self.id, Some(self.status), next, now)
+                .with_stage_started_at(self.updated_at)
+        });
+
+        self.status = next;
+        self.updated_at = now;
+
+        Ok(())
+    }
+
+    /// Inicia uma nova tentativa: o job pendente passa para downloading
+    pub fn start(&mut self) -> Result<(), JobStatusError> {
+        if self.status != JobStatus::Pending {
+            return Err(JobStatusError::InvalidTransition {
+                from: self.status,
+                to: JobStatus::Downloading,
+            });
+        }
+
+        self.transition_to(JobStatus::Downloading)?;
+        self.attempts += 1;
+        self.next_attempt_at = None;
+
+        Ok(())
+    }
+
+    /// Reivindica um job pendente para o worker, iniciando a tentativa e
+    /// registrando até quando o worker tem a posse do job
+    pub fn claim(
+        &mut self,
+        worker_id: impl Into<String>,
+        lease: chrono::Duration,
+    ) -> Result<(), JobStatusError> {
+        // O worker é definido antes da transição para constar no evento
+        let previous = self.worker_id.replace(worker_id.into());
+        self.start().inspect_err(|_| self.worker_id = previous)?;
+        self.lease_expires_at = Some(self.updated_at + lease);
+
+        Ok(())
+    }
+
+    /// Estende a reivindicação do worker por mais `lease` a partir de agora
+    pub fn renew_lease(&mut self, lease: chrono::Duration) {
+        if self.worker_id.is_some() {
+            self.lease_expires_at = Some(Utc::now() + lease);
+        }
+    }
+
+    pub fn has_attempts_left(&self) -> bool {
+        self.attempts < self.max_attempts
+    }
+
+    /// Marca o job como falho, registrando a mensagem de erro
+    pub fn fail(&mut self, error: impl Into<String>) -> Result<(), JobStatusError> {
+        let stage = self.status;
+        let error = error.into();
+
+        self.transition_with(JobStatus::Failed, Some(error.clone()))?;
+        self.record_error(stage, error);
+
+        Ok(())
+    }
+
+    /// Registra a falha da tentativa atual. Com `retry_at` e tentativas
+    /// restantes o job aguarda em retrying; caso contrário falha de vez
+    pub fn fail_attempt(
+        &mut self,
+        error: impl Into<String>,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), JobStatusError> {
+        let Some(retry_at) = retry_at.filter(|_| self.has_attempts_left()) else {
+            return self.fail(error);
+        };
+        let stage = self.status;
+        let error = error.into();
+
+        self.transition_with(JobStatus::Retrying, Some(error.clone()))?;
+        self.next_attempt_at = Some(retry_at);
+        self.worker_id = None;
+        self.lease_expires_at = None;
+        self.record_error(stage, error);
+
+        Ok(())
+    }
+
+    /// Devolve um job em retrying para pending, para que seja reivindicado de novo
+    pub fn requeue(&mut self) -> Result<(), JobStatusError> {
+        self.transition_to(JobStatus::Pending)?;
+        self.next_attempt_at = None;
+
+        Ok(())
+    }
+
+    fn record_error(&mut self, stage: JobStatus, error: String) {
+        self.attempt_errors.push(AttemptError {
+            attempt: self.attempts,
+            stage,
+            error: error.clone(),
+            failed_at: self.updated_at,
+        });
+        self.error = Some(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_job() -> Job {
+        let video = Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string());
+        Job::new("/output/path".to_string(), Arc::new(video))
+    }
+
+    #[test]
+    fn test_job_walks_through_every_stage() {
+        let mut job = new_job();
+        assert_eq!(job.status, JobStatus::Pending);
+
+        for next in [
+            JobStatus::Downloading,
+            JobStatus::Fragmenting,
+            JobStatus::Encoding,
+            JobStatus::Uploading,
+            JobStatus::Finishing,
+            JobStatus::Completed,
+        ] {
+            job.transition_to(next).expect("Transition should be valid");
+        }
+
+        assert_eq!(job.status, JobStatus::Completed);
+        assert_eq!(
+            job.transition_to(JobStatus::Downloading),
+            Err(JobStatusError::InvalidTransition {
+                from: JobStatus::Completed,
+                to: JobStatus::Downloading,
+            })
+        );
+        assert!(job.fail("late failure").is_err());
+        assert_eq!(job.error, None);
+    }
+
+    #[test]
+    fn test_job_fail_records_error() {
+        let mut job = new_job();
+        job.transition_to(JobStatus::Downloading).unwrap();
+
+        job.fail("bucket not found")
+            .expect("Failed to mark job as failed");
+
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.error.as_deref(), Some("bucket not found"));
+    }
+
+    #[test]
+    fn test_job_claim_records_worker_and_lease() {
+        let mut job = new_job();
+
+        job.claim("worker-1", chrono::Duration::minutes(5))
+            .expect("Failed to claim job");
+
+        assert_eq!(job.status, JobStatus::Downloading);
+        assert_eq!(job.worker_id.as_deref(), Some("worker-1"));
+        assert_eq!(
+            job.lease_expires_at,
+            Some(job.updated_at + chrono::Duration::minutes(5))
+        );
+
+        // Apenas jobs pendentes podem ser reivindicados
+        assert!(job.claim("worker-2", chrono::Duration::minutes(5)).is_err());
+        assert_eq!(job.worker_id.as_deref(), Some("worker-1"));
+    }
+
+    #[test]
+    fn test_job_fail_attempt_retries_until_budget_runs_out() {
+        let mut job = new_job();
+        job.max_attempts = 2;
+        let retry_at = Utc::now() + chrono::Duration::seconds(30);
+
+        job.start().expect("Failed to start job");
+        job.transition_to(JobStatus::Fragmenting).unwrap();
+        job.fail_attempt("mp4fragment crashed", Some(retry_at))
+            .expect("Failed to fail attempt");
+
+        assert_eq!(job.status, JobStatus::Retrying);
+        assert_eq!(job.next_attempt_at, Some(retry_at));
+        assert_eq!(job.error.as_deref(), Some("mp4fragment crashed"));
+
+        job.requeue().expect("Failed to requeue job");
+        job.start().expect("Failed to start job");
+        assert_eq!(job.attempts, 2);
+        assert_eq!(job.next_attempt_at, None);
+
+        // Sem tentativas restantes a falha é definitiva
+        job.fail_attempt("bucket unavailable", Some(retry_at))
+            .expect("Failed to fail attempt");
+        assert_eq!(job.status, JobStatus::Failed);
+
+        let errors: Vec<_> = job
+            .attempt_errors
+            .iter()
+            .map(|e| (e.attempt, e.stage, e.error.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (1, JobStatus::Fragmenting, "mp4fragment crashed"),
+                (2, JobStatus::Downloading, "bucket unavailable"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_job_records_an_event_per_transition() {
+        let mut job = new_job();
+        job.claim("worker-1", chrono::Duration::minutes(5)).unwrap();
+        job.fail_attempt("download timed out", Some(Utc::now()))
+            .unwrap();
+
+        let transitions: Vec<_> = job
+            .events
+            .iter()
+            .map(|e| (e.from_status, e.to_status, e.worker_id.as_deref()))
+            .collect();
+        assert_eq!(
+            transitions,
+            vec![
+                (None, JobStatus::Pending, None),
+                (
+                    Some(JobStatus::Pending),
+                    JobStatus::Downloading,
+                    Some("worker-1")
+                ),
+                (
+                    Some(JobStatus::Downloading),
+                    JobStatus::Retrying,
+                    Some("worker-1")
+                ),
+            ]
+        );
+
+        let failure = job.events.last().unwrap();
+        assert_eq!(failure.message.as_deref(), Some("download timed out"));
+        assert!(failure.stage_duration_ms.is_some_and(|ms| ms >= 0));
+        assert_eq!(failure.occurred_at, job.updated_at);
+        assert!(job.events.windows(2).all(|pair| pair[0].id < pair[1].id));
+
+        // Transições rejeitadas não geram eventos
+        assert!(job.transition_to(JobStatus::Completed).is_err());
+        assert_eq!(job.events.len(), 3);
+    }
+
+    #[test]
+    fn test_job_validate() {
+        let job = new_job();
+        assert!(job.validate().is_ok());
+
+        let mut job = new_job();
+        job.id = Uuid::nil();
+        job.output_bucket_path = String::new();
+        job.video_id = Uuid::new_v4();
+
+        let error = job.validate().expect_err("Job should be invalid");
+        let fields: Vec<_> = error.fields.iter().map(|f| f.field).collect();
+
+        assert_eq!(fields, vec!["id", "output_bucket_path", "video_id"]);
+        assert_eq!(
+            error.to_string(),
+            "invalid job: id must be a valid uuid; output_bucket_path must not be empty; video_id must match the associated video"
+        );
+    }
+
+    #[test]
+    fn test_job_status_serializes_as_string() {
+        let job = new_job();
+        let json = serde_json::to_value(&job).expect("Failed to serialize job");
+
+        assert_eq!(json["status"], "pending");
+        assert_eq!(json["job_id"], job.id.to_string());
     }
 }
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::JobStatus;
+
+/// Uma mudança de status do job, gravada na tabela job_events
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct JobEvent {
+    /// UUIDv7: ordena os eventos na ordem em que foram gerados
+    pub id: Uuid,
+    pub job_id: Uuid,
+    /// `None` no evento de criação do job
+    pub from_status: Option<JobStatus>,
+    pub to_status: JobStatus,
+    pub worker_id: Option<String>,
+    /// Erro que causou a transição, em retrying e failed
+    pub message: Option<String>,
+    /// Quanto tempo o job ficou em `from_status`
+    pub stage_duration_ms: Option<i64>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl JobEvent {
+    pub fn new(
+        job_id: Uuid,
+        from_status: Option<JobStatus>,
+        to_status: JobStatus,
+        occurred_at: DateTime<Utc>,
+    ) -> JobEvent {
+        JobEvent {
+            id: Uuid::now_v7(),
+            job_id,
+            from_status,
+            to_status,
+            worker_id: None,
+            message: None,
+            stage_duration_ms: None,
+            occurred_at,
+        }
+    }
+
+    /// Registra a duração do estágio anterior como o tempo desde `entered_at`
+    pub fn with_stage_started_at(mut self, entered_at: DateTime<Utc>) -> JobEvent {
+        self.stage_duration_ms = Some((self.occurred_at - entered_at).num_milliseconds().max(0));
+        self
+    }
+
+    pub fn stage_duration(&self) -> Option<chrono::Duration> {
+        self.stage_duration_ms.map(chrono::Duration::milliseconds)
+    }
+}
@@ -0,0 +1,164 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Pending,
+    Downloading,
+    Fragmenting,
+    Encoding,
+    Uploading,
+    Finishing,
+    /// A tentativa falhou e o job aguarda `next_attempt_at` para voltar a pending
+    Retrying,
+    Completed,
+    Failed,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Downloading => "downloading",
+            JobStatus::Fragmenting => "fragmenting",
+            JobStatus::Encoding => "encoding",
+            JobStatus::Uploading => "uploading",
+            JobStatus::Finishing => "finishing",
+            JobStatus::Retrying => "retrying",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+        }
+    }
+
+    /// Estados finais não aceitam mais nenhuma transição
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed)
+    }
+
+    /// Etapas em que um worker está processando o job
+    pub fn is_active(&self) -> bool {
+        use JobStatus::*;
+
+        matches!(
+            self,
+            Downloading | Fragmenting | Encoding | Uploading | Finishing
+        )
+    }
+
+    /// Regras da máquina de estados: cada etapa só avança para a seguinte,
+    /// qualquer etapa ativa pode aguardar uma nova tentativa e qualquer
+    /// estado não-final pode falhar
+    pub fn can_transition_to(&self, next: JobStatus) -> bool {
+        use JobStatus::*;
+
+        match (self, next) {
+            (Pending, Downloading)
+            | (Downloading, Fragmenting)
+            | (Fragmenting, Encoding)
+            | (Encoding, Uploading)
+            | (Uploading, Finishing)
+            | (Finishing, Completed)
+            | (Retrying, Pending) => true,
+            (current, Retrying) => current.is_active(),
+            (current, Failed) => !current.is_terminal(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobStatus {
+    type Err = JobStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(JobStatus::Pending),
+            "downloading" => Ok(JobStatus::Downloading),
+            "fragmenting" => Ok(JobStatus::Fragmenting),
+            "encoding" => Ok(JobStatus::Encoding),
+            "uploading" => Ok(JobStatus::Uploading),
+            "finishing" => Ok(JobStatus::Finishing),
+            "retrying" => Ok(JobStatus::Retrying),
+            "completed" => Ok(JobStatus::Completed),
+            "failed" => Ok(JobStatus::Failed),
+            other => Err(JobStatusError::Unknown(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobStatusError {
+    InvalidTransition { from: JobStatus, to: JobStatus },
+    Unknown(String),
+}
+
+impl fmt::Display for JobStatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobStatusError::InvalidTransition { from, to } => {
+                write!(f, "invalid job status transition from {} to {}", from, to)
+            }
+            JobStatusError::Unknown(status) => write!(f, "unknown job status: {}", status),
+        }
+    }
+}
+
+impl std::error::Error for JobStatusError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_status_round_trip_from_str() {
+        let all = [
+            JobStatus::Pending,
+            JobStatus::Downloading,
+            JobStatus::Fragmenting,
+            JobStatus::Encoding,
+            JobStatus::Uploading,
+            JobStatus::Finishing,
+            JobStatus::Retrying,
+            JobStatus::Completed,
+            JobStatus::Failed,
+        ];
+
+        for status in all {
+            assert_eq!(status.as_str().parse::<JobStatus>(), Ok(status));
+            assert_eq!(
+                serde_json::to_string(&status).unwrap(),
+                format!("\"{}\"", status)
+            );
+        }
+
+        assert_eq!(
+            "unknown".parse::<JobStatus>(),
+            Err(JobStatusError::Unknown("unknown".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_job_status_transitions() {
+        assert!(JobStatus::Pending.can_transition_to(JobStatus::Downloading));
+        assert!(JobStatus::Finishing.can_transition_to(JobStatus::Completed));
+        assert!(JobStatus::Encoding.can_transition_to(JobStatus::Failed));
+
+        assert!(!JobStatus::Pending.can_transition_to(JobStatus::Encoding));
+        assert!(!JobStatus::Completed.can_transition_to(JobStatus::Downloading));
+        assert!(!JobStatus::Completed.can_transition_to(JobStatus::Failed));
+        assert!(!JobStatus::Failed.can_transition_to(JobStatus::Failed));
+
+        assert!(JobStatus::Encoding.can_transition_to(JobStatus::Retrying));
+        assert!(JobStatus::Retrying.can_transition_to(JobStatus::Pending));
+        assert!(JobStatus::Retrying.can_transition_to(JobStatus::Failed));
+        assert!(!JobStatus::Pending.can_transition_to(JobStatus::Retrying));
+        assert!(!JobStatus::Retrying.can_transition_to(JobStatus::Downloading));
+    }
+}
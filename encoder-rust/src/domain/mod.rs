@@ -1,5 +1,13 @@
+mod checkpoints;
 mod job;
+mod job_event;
+mod job_status;
+mod validation;
 mod video;
 
-pub use job::Job;
+pub use checkpoints::{EncodeCheckpoint, FileCheckpoint, JobCheckpoints, OutputFile};
+pub use job::{AttemptError, DEFAULT_MAX_ATTEMPTS, Job};
+pub use job_event::JobEvent;
+pub use job_status::{JobStatus, JobStatusError};
+pub use validation::{FieldError, ValidationError};
 pub use video::Video;
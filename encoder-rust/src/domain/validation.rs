@@ -0,0 +1,60 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: &'static str,
+}
+
+/// Erro de validação de uma entidade, listando todos os campos inválidos
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub entity: &'static str,
+    pub fields: Vec<FieldError>,
+}
+
+impl ValidationError {
+    pub fn new(entity: &'static str) -> Self {
+        ValidationError {
+            entity,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, field: &'static str, message: &'static str) {
+        self.fields.push(FieldError { field, message });
+    }
+
+    /// Verifica se uma string obrigatória foi preenchida (equivalente ao `notnull` do Go)
+    pub fn require_not_blank(&mut self, field: &'static str, value: &str) {
+        if value.trim().is_empty() {
+            self.add(field, "must not be empty");
+        }
+    }
+
+    /// Retorna Ok se nenhum campo falhou, ou o próprio erro caso contrário
+    pub fn into_result(self) -> Result<(), ValidationError> {
+        if self.fields.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {}: ", self.entity)?;
+
+        for (i, field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{} {}", field.field, field.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::Job;
+use crate::domain::{Job, ValidationError};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Video {
@@ -28,4 +28,59 @@ impl Video {
             jobs: Vec::new(),
         }
     }
+
+    /// Valida os campos obrigatórios, assim como o `Validate()` do encoder em Go
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::new("video");
+
+        if self.id.is_nil() {
+            errors.add("id", "must be a valid uuid");
+        }
+        errors.require_not_blank("resource_id", &self.resource_id);
+        errors.require_not_blank("file_path", &self.file_path);
+
+        errors.into_result()
+    }
+
+    /// Chave que identifica submissões do mesmo arquivo para o mesmo recurso.
+    /// Serializada como array JSON para que nenhum par de valores colida
+    pub fn idempotency_key(&self) -> String {
+        serde_json::json!([self.resource_id, self.file_path]).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_video_validate() {
+        let video = Video::new("resource_123".to_string(), "/path/to/video.mp4".to_string());
+        assert!(video.validate().is_ok());
+
+        let mut video = Video::new("".to_string(), "  ".to_string());
+        video.id = Uuid::nil();
+
+        let error = video.validate().expect_err("Video should be invalid");
+        let fields: Vec<_> = error.fields.iter().map(|f| f.field).collect();
+
+        assert_eq!(error.entity, "video");
+        assert_eq!(fields, vec!["id", "resource_id", "file_path"]);
+    }
+
+    #[test]
+    fn test_video_idempotency_key() {
+        let video = Video::new("resource_123".to_string(), "videos/a.mp4".to_string());
+        let same = Video::new("resource_123".to_string(), "videos/a.mp4".to_string());
+        assert_eq!(video.idempotency_key(), same.idempotency_key());
+        assert_eq!(
+            video.idempotency_key(),
+            r#"["resource_123","videos/a.mp4"]"#
+        );
+
+        // O separador não pode fazer pares diferentes gerarem a mesma chave
+        let left = Video::new("a\",\"b".to_string(), "c".to_string());
+        let right = Video::new("a".to_string(), "b\",\"c".to_string());
+        assert_ne!(left.idempotency_key(), right.idempotency_key());
+    }
 }
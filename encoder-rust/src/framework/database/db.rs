@@ -1,6 +1,7 @@
-use sqlx::{Pool, Postgres, Sqlite, sqlite::SqlitePoolOptions};
+use sqlx::{Pool, Postgres, Sqlite, postgres::PgPoolOptions, sqlite::SqlitePoolOptions};
+
+use crate::config::DatabaseConfig;
 
-#[derive(Clone)]
 pub struct Database<T>
 where
     T: sqlx::Database,
@@ -8,6 +9,18 @@ where
     pub conn: Pool<T>,
 }
 
+// O derive exigiria `T: Clone`, mas apenas o pool precisa ser clonado
+impl<T> Clone for Database<T>
+where
+    T: sqlx::Database,
+{
+    fn clone(&self) -> Self {
+        Database {
+            conn: self.conn.clone(),
+        }
+    }
+}
+
 impl Database<Postgres> {
     pub async fn new(uri: String, auto_migrate: Option<bool>) -> Result<Self, sqlx::Error> {
         let db = Pool::<Postgres>::connect(&uri).await?;
@@ -18,6 +31,20 @@ impl Database<Postgres> {
 
         Ok(Database { conn: db })
     }
+
+    /// Conecta com a url, o tamanho do pool e o auto-migrate da configuração
+    pub async fn from_config(config: &DatabaseConfig) -> Result<Self, sqlx::Error> {
+        let db = PgPoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect(&config.url)
+            .await?;
+
+        if config.auto_migrate {
+            sqlx::migrate!().run(&db).await?;
+        }
+
+        Ok(Database { conn: db })
+    }
 }
 
 impl Database<Sqlite> {
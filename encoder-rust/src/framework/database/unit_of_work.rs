@@ -0,0 +1,43 @@
+use sqlx::Transaction;
+
+use crate::framework::Database;
+
+/// Transação aberta a partir de um `Database`. As operações dos repositórios
+/// ligadas a ela só ficam visíveis após `commit`; se a unidade de trabalho for
+/// descartada sem commit, a transação é desfeita
+pub struct UnitOfWork<DB>
+where
+    DB: sqlx::Database,
+{
+    tx: Transaction<'static, DB>,
+}
+
+impl<DB> UnitOfWork<DB>
+where
+    DB: sqlx::Database,
+{
+    /// Conexão da transação, usada pelas queries dos repositórios
+    pub fn connection(&mut self) -> &mut DB::Connection {
+        &mut self.tx
+    }
+
+    pub async fn commit(self) -> Result<(), sqlx::Error> {
+        self.tx.commit().await
+    }
+
+    pub async fn rollback(self) -> Result<(), sqlx::Error> {
+        self.tx.rollback().await
+    }
+}
+
+impl<DB> Database<DB>
+where
+    DB: sqlx::Database,
+{
+    /// Abre uma transação no pool (funciona tanto para Postgres quanto para SQLite)
+    pub async fn begin(&self) -> Result<UnitOfWork<DB>, sqlx::Error> {
+        Ok(UnitOfWork {
+            tx: self.conn.begin().await?,
+        })
+    }
+}
@@ -1,7 +1,44 @@
 pub mod database {
     mod db;
+    mod unit_of_work;
 
     pub use db::Database;
+    pub use unit_of_work::UnitOfWork;
 }
 
-pub use database::Database;
+pub mod queue {
+    mod delivery;
+    // Broker em memória usado apenas pelos testes dos serviços
+    #[cfg(test)]
+    mod in_memory;
+    mod rabbitmq;
+
+    pub use delivery::{
+        ATTEMPT_HEADER, DeadLetter, Delivery, FAILURE_REASON_HEADER, FAILURE_STAGE_HEADER,
+        Publisher, QueueError,
+    };
+    #[cfg(test)]
+    pub use in_memory::{
+        DeadLetteredMessage, DeliveryOutcome, InMemoryBroker, InMemoryDelivery, PublishedMessage,
+    };
+    pub use rabbitmq::{RabbitMq, RabbitMqConfig, RabbitMqDelivery};
+}
+
+pub mod storage {
+    mod gcs_store;
+    mod local_store;
+    mod object_store;
+
+    pub use gcs_store::GcsObjectStore;
+    pub use local_store::{LocalObjectStore, collect_files, open_file};
+    pub use object_store::{
+        ByteStream, ObjectReader, ObjectStore, ObjectStoreError, StoredObject, content_type,
+    };
+}
+
+pub use database::{Database, UnitOfWork};
+pub use queue::{DeadLetter, Delivery, Publisher, QueueError};
+pub use storage::{
+    ByteStream, GcsObjectStore, LocalObjectStore, ObjectReader, ObjectStore, ObjectStoreError,
+    StoredObject,
+};
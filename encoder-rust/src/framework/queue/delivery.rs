@@ -0,0 +1,87 @@
+#[derive(Debug)]
+pub struct QueueError(pub String);
+
+impl std::fmt::Display for QueueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Queue error: {}", self.0)
+    }
+}
+
+impl std::error::Error for QueueError {}
+
+/// Header com o número da tentativa de processamento da mensagem (a primeira é 1)
+pub const ATTEMPT_HEADER: &str = "x-attempt";
+pub const FAILURE_REASON_HEADER: &str = "x-failure-reason";
+pub const FAILURE_STAGE_HEADER: &str = "x-failure-stage";
+
+/// Motivo pelo qual uma mensagem foi enviada para a dead-letter exchange
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetter {
+    pub reason: String,
+    pub stage: String,
+    pub attempts: u32,
+}
+
+impl DeadLetter {
+    pub fn new(reason: impl Into<String>, stage: impl Into<String>, attempts: u32) -> Self {
+        DeadLetter {
+            reason: reason.into(),
+            stage: stage.into(),
+            attempts,
+        }
+    }
+}
+
+/// Publicação de mensagens em uma exchange
+#[allow(async_fn_in_trait)]
+pub trait Publisher: Send + Sync {
+    async fn publish(
+        &self,
+        exchange: &str,
+        routing_key: &str,
+        body: &[u8],
+    ) -> Result<(), QueueError>;
+
+    /// Publica na dead-letter exchange, com os headers de falha, uma mensagem
+    /// que não está mais na fila (ex.: um job que falhou de vez numa nova
+    /// tentativa, depois que a entrega original já foi confirmada)
+    async fn publish_dead_letter(&self, body: &[u8], letter: &DeadLetter)
+    -> Result<(), QueueError>;
+}
+
+/// Permite compartilhar o publisher com quem precisa fechar a conexão
+impl<P: Publisher> Publisher for std::sync::Arc<P> {
+    async fn publish(
+        &self,
+        exchange: &str,
+        routing_key: &str,
+        body: &[u8],
+    ) -> Result<(), QueueError> {
+        P::publish(self, exchange, routing_key, body).await
+    }
+
+    async fn publish_dead_letter(
+        &self,
+        body: &[u8],
+        letter: &DeadLetter,
+    ) -> Result<(), QueueError> {
+        P::publish_dead_letter(self, body, letter).await
+    }
+}
+
+/// Mensagem recebida da fila, que precisa ser confirmada (ack), rejeitada ou
+/// enviada para a dead-letter exchange
+#[allow(async_fn_in_trait)]
+pub trait Delivery: Send + Sync {
+    fn body(&self) -> &[u8];
+
+    /// Número da tentativa atual, lido do header `x-attempt`
+    fn attempt(&self) -> u32;
+
+    async fn ack(&self) -> Result<(), QueueError>;
+    async fn reject(&self, requeue: bool) -> Result<(), QueueError>;
+
+    /// Publica a mensagem na dead-letter exchange com os headers de falha
+    /// e confirma a original, para que ela não volte a bloquear a fila
+    async fn dead_letter(&self, letter: &DeadLetter) -> Result<(), QueueError>;
+}
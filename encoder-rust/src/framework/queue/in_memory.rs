@@ -0,0 +1,182 @@
+use std::sync::{Arc, Mutex};
+
+use futures::Stream;
+use futures::StreamExt;
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::framework::queue::{DeadLetter, Delivery, Publisher, QueueError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    Pending,
+    Acked,
+    Rejected { requeue: bool },
+    DeadLettered(DeadLetter),
+}
+
+/// Mensagem publicada em uma exchange do broker em memória
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedMessage {
+    pub exchange: String,
+    pub routing_key: String,
+    pub body: Vec<u8>,
+}
+
+/// Mensagem publicada diretamente na dead-letter exchange do broker em memória
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetteredMessage {
+    pub body: Vec<u8>,
+    pub letter: DeadLetter,
+}
+
+/// Mensagem do broker em memória; clones compartilham o mesmo resultado (ack/reject)
+#[derive(Debug, Clone)]
+pub struct InMemoryDelivery {
+    body: Vec<u8>,
+    outcome: Arc<Mutex<DeliveryOutcome>>,
+}
+
+impl InMemoryDelivery {
+    pub fn new(body: impl Into<Vec<u8>>) -> Self {
+        InMemoryDelivery {
+            body: body.into(),
+            outcome: Arc::new(Mutex::new(DeliveryOutcome::Pending)),
+        }
+    }
+
+    pub fn outcome(&self) -> DeliveryOutcome {
+        self.outcome.lock().unwrap().clone()
+    }
+
+    fn settle(&self, outcome: DeliveryOutcome) -> Result<(), QueueError> {
+        let mut current = self.outcome.lock().unwrap();
+
+        if *current != DeliveryOutcome::Pending {
+            return Err(QueueError("delivery already settled".to_string()));
+        }
+
+        *current = outcome;
+        Ok(())
+    }
+}
+
+impl Delivery for InMemoryDelivery {
+    fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    fn attempt(&self) -> u32 {
+        1
+    }
+
+    async fn ack(&self) -> Result<(), QueueError> {
+        self.settle(DeliveryOutcome::Acked)
+    }
+
+    async fn reject(&self, requeue: bool) -> Result<(), QueueError> {
+        self.settle(DeliveryOutcome::Rejected { requeue })
+    }
+
+    async fn dead_letter(&self, letter: &DeadLetter) -> Result<(), QueueError> {
+        self.settle(DeliveryOutcome::DeadLettered(letter.clone()))
+    }
+}
+
+/// Broker em processo que substitui o RabbitMQ nos testes; clones compartilham a mesma fila
+#[derive(Clone)]
+pub struct InMemoryBroker {
+    sender: UnboundedSender<InMemoryDelivery>,
+    receiver: Arc<Mutex<Option<UnboundedReceiver<InMemoryDelivery>>>>,
+    published: Arc<Mutex<Vec<PublishedMessage>>>,
+    dead_lettered: Arc<Mutex<Vec<DeadLetteredMessage>>>,
+}
+
+impl InMemoryBroker {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+
+        InMemoryBroker {
+            sender,
+            receiver: Arc::new(Mutex::new(Some(receiver))),
+            published: Arc::new(Mutex::new(Vec::new())),
+            dead_lettered: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Enfileira uma mensagem e retorna um handle para inspecionar o ack/reject
+    pub fn send(&self, body: impl Into<Vec<u8>>) -> InMemoryDelivery {
+        let delivery = InMemoryDelivery::new(body);
+
+        self.sender
+            .unbounded_send(delivery.clone())
+            .expect("InMemoryBroker is closed");
+
+        delivery
+    }
+
+    /// Mensagens publicadas até o momento, em ordem
+    pub fn published(&self) -> Vec<PublishedMessage> {
+        self.published.lock().unwrap().clone()
+    }
+
+    /// Mensagens publicadas diretamente na dead-letter exchange, em ordem
+    pub fn dead_lettered(&self) -> Vec<DeadLetteredMessage> {
+        self.dead_lettered.lock().unwrap().clone()
+    }
+
+    /// Encerra a fila; o stream de consumo termina após as mensagens pendentes
+    pub fn close(&self) {
+        self.sender.close_channel();
+    }
+
+    /// Retorna o stream de mensagens (só pode ser chamado uma vez)
+    pub fn consume(&self) -> impl Stream<Item = Result<InMemoryDelivery, QueueError>> + use<> {
+        let receiver = self
+            .receiver
+            .lock()
+            .unwrap()
+            .take()
+            .expect("InMemoryBroker already has a consumer");
+
+        receiver.map(Ok)
+    }
+}
+
+impl Publisher for InMemoryBroker {
+    async fn publish(
+        &self,
+        exchange: &str,
+        routing_key: &str,
+        body: &[u8],
+    ) -> Result<(), QueueError> {
+        self.published.lock().unwrap().push(PublishedMessage {
+            exchange: exchange.to_string(),
+            routing_key: routing_key.to_string(),
+            body: body.to_vec(),
+        });
+
+        Ok(())
+    }
+
+    async fn publish_dead_letter(
+        &self,
+        body: &[u8],
+        letter: &DeadLetter,
+    ) -> Result<(), QueueError> {
+        self.dead_lettered
+            .lock()
+            .unwrap()
+            .push(DeadLetteredMessage {
+                body: body.to_vec(),
+                letter: letter.clone(),
+            });
+
+        Ok(())
+    }
+}
+
+impl Default for InMemoryBroker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
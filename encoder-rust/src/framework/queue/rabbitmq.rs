@@ -0,0 +1,334 @@
+use futures::{Stream, StreamExt};
+use lapin::options::{
+    BasicAckOptions, BasicConsumeOptions, BasicPublishOptions, BasicQosOptions, BasicRejectOptions,
+    ConfirmSelectOptions, ExchangeDeclareOptions, QueueBindOptions, QueueDeclareOptions,
+};
+use lapin::types::{AMQPValue, FieldTable};
+use lapin::{BasicProperties, Channel, Connection, ConnectionProperties, ExchangeKind};
+
+use crate::framework::queue::{
+    ATTEMPT_HEADER, DeadLetter, Delivery, FAILURE_REASON_HEADER, FAILURE_STAGE_HEADER, Publisher,
+    QueueError,
+};
+
+/// Configuração de conexão com o RabbitMQ, lida das variáveis RABBITMQ_* pelo `Config`
+#[derive(Debug, Clone)]
+pub struct RabbitMqConfig {
+    pub user: String,
+    pub password: String,
+    pub host: String,
+    pub port: u16,
+    pub vhost: String,
+    pub consumer_name: String,
+    pub consumer_queue_name: String,
+    pub notification_exchange: String,
+    pub notification_routing_key: String,
+    pub dead_letter_exchange: String,
+    /// Fila ligada à dead-letter exchange, onde as mensagens descartadas ficam
+    /// disponíveis para inspeção
+    pub dead_letter_queue: String,
+}
+
+impl RabbitMqConfig {
+    pub fn uri(&self) -> String {
+        // O vhost padrão "/" precisa ser codificado na URI AMQP
+        let vhost = match self.vhost.as_str() {
+            "/" => "%2f",
+            vhost => vhost.trim_start_matches('/'),
+        };
+
+        format!(
+            "amqp://{}:{}@{}:{}/{}",
+            self.user, self.password, self.host, self.port, vhost
+        )
+    }
+}
+
+pub struct RabbitMq {
+    connection: Connection,
+    channel: Channel,
+    config: RabbitMqConfig,
+}
+
+impl RabbitMq {
+    pub async fn connect(config: RabbitMqConfig) -> Result<Self, QueueError> {
+        let connection = Connection::connect(&config.uri(), ConnectionProperties::default())
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        let channel = connection
+            .create_channel()
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        // Com publisher confirms o broker responde cada publicação com ack ou nack
+        channel
+            .confirm_select(ConfirmSelectOptions::default())
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        Ok(RabbitMq {
+            connection,
+            channel,
+            config,
+        })
+    }
+
+    /// Declara a dead-letter exchange com a sua fila e a fila de consumo e
+    /// retorna o stream de mensagens, limitando a quantidade de mensagens não
+    /// confirmadas a `prefetch`
+    pub async fn consume(
+        &self,
+        prefetch: u16,
+    ) -> Result<impl Stream<Item = Result<RabbitMqDelivery, QueueError>> + use<>, QueueError> {
+        self.channel
+            .basic_qos(prefetch, BasicQosOptions::default())
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        self.channel
+            .exchange_declare(
+                &self.config.dead_letter_exchange,
+                ExchangeKind::Fanout,
+                ExchangeDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        // Sem uma fila ligada, a exchange fanout descartaria as mensagens
+        self.channel
+            .queue_declare(
+                &self.config.dead_letter_queue,
+                QueueDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        self.channel
+            .queue_bind(
+                &self.config.dead_letter_queue,
+                &self.config.dead_letter_exchange,
+                "",
+                QueueBindOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        // Mensagens rejeitadas sem requeue também seguem para a DLX. Uma fila
+        // criada antes sem esse argumento faz a declaração falhar com
+        // PRECONDITION_FAILED e precisa ser recriada
+        let mut arguments = FieldTable::default();
+        arguments.insert(
+            "x-dead-letter-exchange".into(),
+            AMQPValue::LongString(self.config.dead_letter_exchange.clone().into()),
+        );
+
+        self.channel
+            .queue_declare(
+                &self.config.consumer_queue_name,
+                QueueDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                arguments,
+            )
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        let consumer = self
+            .channel
+            .basic_consume(
+                &self.config.consumer_queue_name,
+                &self.config.consumer_name,
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .map_err(|e| QueueError(e.to_string()))?;
+
+        let channel = self.channel.clone();
+        let queue = self.config.consumer_queue_name.clone();
+        let dead_letter_exchange = self.config.dead_letter_exchange.clone();
+
+        Ok(consumer.map(move |delivery| {
+            delivery
+                .map(|delivery| RabbitMqDelivery {
+                    delivery,
+                    channel: channel.clone(),
+                    queue: queue.clone(),
+                    dead_letter_exchange: dead_letter_exchange.clone(),
+                })
+                .map_err(|e| QueueError(e.to_string()))
+        }))
+    }
+
+    /// Fecha a conexão; mensagens recebidas e não confirmadas voltam para a fila
+    pub async fn close(&self) -> Result<(), QueueError> {
+        self.connection
+            .close(200, "shutdown")
+            .await
+            .map_err(|e| QueueError(e.to_string()))
+    }
+}
+
+impl Publisher for RabbitMq {
+    /// Publica uma mensagem persistente em JSON e aguarda a confirmação do broker
+    async fn publish(
+        &self,
+        exchange: &str,
+        routing_key: &str,
+        body: &[u8],
+    ) -> Result<(), QueueError> {
+        publish(
+            &self.channel,
+            exchange,
+            routing_key,
+            body,
+            FieldTable::default(),
+        )
+        .await
+    }
+
+    /// Usa a fila de consumo como routing key, como as mensagens rejeitadas
+    async fn publish_dead_letter(
+        &self,
+        body: &[u8],
+        letter: &DeadLetter,
+    ) -> Result<(), QueueError> {
+        publish(
+            &self.channel,
+            &self.config.dead_letter_exchange,
+            &self.config.consumer_queue_name,
+            body,
+            dead_letter_headers(FieldTable::default(), letter),
+        )
+        .await
+    }
+}
+
+/// Acrescenta aos headers o motivo, o estágio e as tentativas da falha
+fn dead_letter_headers(mut headers: FieldTable, letter: &DeadLetter) -> FieldTable {
+    headers.insert(
+        FAILURE_REASON_HEADER.into(),
+        AMQPValue::LongString(letter.reason.clone().into()),
+    );
+    headers.insert(
+        FAILURE_STAGE_HEADER.into(),
+        AMQPValue::LongString(letter.stage.clone().into()),
+    );
+    headers.insert(ATTEMPT_HEADER.into(), AMQPValue::LongUInt(letter.attempts));
+
+    headers
+}
+
+/// Publica uma mensagem persistente em JSON com os headers informados e
+/// aguarda a confirmação do broker. Um nack é retornado como erro
+async fn publish(
+    channel: &Channel,
+    exchange: &str,
+    routing_key: &str,
+    body: &[u8],
+    headers: FieldTable,
+) -> Result<(), QueueError> {
+    let properties = BasicProperties::default()
+        .with_content_type("application/json".into())
+        .with_delivery_mode(2)
+        .with_headers(headers);
+
+    let confirmation = channel
+        .basic_publish(
+            exchange,
+            routing_key,
+            BasicPublishOptions::default(),
+            body,
+            properties,
+        )
+        .await
+        .map_err(|e| QueueError(e.to_string()))?
+        .await
+        .map_err(|e| QueueError(e.to_string()))?;
+
+    if confirmation.is_nack() {
+        return Err(QueueError(format!(
+            "broker rejected the message published to exchange {:?} with routing key {:?}",
+            exchange, routing_key
+        )));
+    }
+
+    Ok(())
+}
+
+/// Mensagem recebida do RabbitMQ, com o canal usado para republicá-la
+pub struct RabbitMqDelivery {
+    pub delivery: lapin::message::Delivery,
+    pub channel: Channel,
+    pub queue: String,
+    pub dead_letter_exchange: String,
+}
+
+impl RabbitMqDelivery {
+    /// Headers originais da mensagem, preservados nas republicações
+    fn headers(&self) -> FieldTable {
+        self.delivery
+            .properties
+            .headers()
+            .clone()
+            .unwrap_or_default()
+    }
+}
+
+impl Delivery for RabbitMqDelivery {
+    fn body(&self) -> &[u8] {
+        &self.delivery.data
+    }
+
+    fn attempt(&self) -> u32 {
+        let headers = self.headers();
+
+        let attempt = match headers.inner().get(ATTEMPT_HEADER) {
+            Some(AMQPValue::LongUInt(n)) => Some(*n),
+            Some(AMQPValue::LongInt(n)) => u32::try_from(*n).ok(),
+            Some(AMQPValue::LongLongInt(n)) => u32::try_from(*n).ok(),
+            Some(AMQPValue::LongString(s)) => s.to_string().parse().ok(),
+            _ => None,
+        };
+
+        attempt.unwrap_or(1).max(1)
+    }
+
+    async fn ack(&self) -> Result<(), QueueError> {
+        self.delivery
+            .ack(BasicAckOptions::default())
+            .await
+            .map_err(|e| QueueError(e.to_string()))
+    }
+
+    async fn reject(&self, requeue: bool) -> Result<(), QueueError> {
+        self.delivery
+            .reject(BasicRejectOptions { requeue })
+            .await
+            .map_err(|e| QueueError(e.to_string()))
+    }
+
+    async fn dead_letter(&self, letter: &DeadLetter) -> Result<(), QueueError> {
+        publish(
+            &self.channel,
+            &self.dead_letter_exchange,
+            &self.queue,
+            self.body(),
+            dead_letter_headers(self.headers(), letter),
+        )
+        .await?;
+
+        self.ack().await
+    }
+}
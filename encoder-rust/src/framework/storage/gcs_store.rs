@@ -0,0 +1,213 @@
+use std::sync::Mutex;
+
+use futures::{StreamExt, TryStreamExt};
+use google_cloud_storage::client::{Client, ClientConfig};
+use google_cloud_storage::http::Error as GcsError;
+use google_cloud_storage::http::objects::delete::DeleteObjectRequest;
+use google_cloud_storage::http::objects::download::Range;
+use google_cloud_storage::http::objects::get::GetObjectRequest;
+use google_cloud_storage::http::objects::list::ListObjectsRequest;
+use google_cloud_storage::http::objects::upload::{Media, UploadObjectRequest, UploadType};
+
+use crate::framework::storage::{
+    ObjectReader, ObjectStore, ObjectStoreError, StoredObject, content_type,
+};
+
+/// ObjectStore apoiado no Google Cloud Storage
+#[derive(Clone)]
+pub struct GcsObjectStore {
+    client: Client,
+}
+
+impl GcsObjectStore {
+    pub fn new(client: Client) -> Self {
+        GcsObjectStore { client }
+    }
+
+    /// Cria o client usando as credenciais padrão (GOOGLE_APPLICATION_CREDENTIALS)
+    pub async fn from_env() -> anyhow::Result<Self> {
+        let config = ClientConfig::default().with_auth().await?;
+
+        Ok(GcsObjectStore::new(Client::new(config)))
+    }
+
+    /// Metadados do upload, com o MIME type derivado da extensão do objeto
+    fn media(object: &str, size: Option<u64>) -> Media {
+        Media {
+            content_type: content_type(object).into(),
+            content_length: size,
+            ..Media::new(object.to_string())
+        }
+    }
+
+    fn map_error(e: GcsError, bucket: &str, object: &str) -> ObjectStoreError {
+        match e {
+            GcsError::Response(response) if response.code == 404 => ObjectStoreError::NotFound {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+            },
+            e => ObjectStoreError::Backend(e.to_string()),
+        }
+    }
+}
+
+impl ObjectStore for GcsObjectStore {
+    async fn get(&self, bucket: &str, object: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        self.client
+            .download_object(
+                &GetObjectRequest {
+                    bucket: bucket.to_string(),
+                    object: object.to_string(),
+                    ..Default::default()
+                },
+                &Range::default(),
+            )
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))
+    }
+
+    async fn get_stream(
+        &self,
+        bucket: &str,
+        object: &str,
+    ) -> Result<ObjectReader, ObjectStoreError> {
+        let request = GetObjectRequest {
+            bucket: bucket.to_string(),
+            object: object.to_string(),
+            ..Default::default()
+        };
+
+        // Os metadados trazem o tamanho total, usado no acompanhamento do progresso
+        let metadata = self
+            .client
+            .get_object(&request)
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))?;
+
+        let stream = self
+            .client
+            .download_streamed_object(&request, &Range::default())
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))?;
+
+        let (bucket, object) = (bucket.to_string(), object.to_string());
+        let stream = stream
+            .map_ok(|chunk| chunk.to_vec())
+            .map_err(move |e| Self::map_error(e, &bucket, &object));
+
+        Ok(ObjectReader {
+            size: metadata.size as u64,
+            stream: stream.boxed(),
+        })
+    }
+
+    async fn put(
+        &self,
+        bucket: &str,
+        object: &str,
+        data: Vec<u8>,
+    ) -> Result<StoredObject, ObjectStoreError> {
+        let uploaded = self
+            .client
+            .upload_object(
+                &UploadObjectRequest {
+                    bucket: bucket.to_string(),
+                    ..Default::default()
+                },
+                data,
+                &UploadType::Simple(Self::media(object, None)),
+            )
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))?;
+
+        Ok(StoredObject {
+            name: uploaded.name,
+            size: uploaded.size as u64,
+        })
+    }
+
+    async fn put_stream(
+        &self,
+        bucket: &str,
+        object: &str,
+        reader: ObjectReader,
+    ) -> Result<StoredObject, ObjectStoreError> {
+        // O client exige um stream `Sync`; o mutex só é disputado pelo próprio upload
+        let stream = Mutex::new(reader.stream);
+        let stream = futures::stream::poll_fn(move |cx| {
+            stream
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .poll_next_unpin(cx)
+        });
+
+        let uploaded = self
+            .client
+            .upload_streamed_object(
+                &UploadObjectRequest {
+                    bucket: bucket.to_string(),
+                    ..Default::default()
+                },
+                stream,
+                &UploadType::Simple(Self::media(object, Some(reader.size))),
+            )
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))?;
+
+        Ok(StoredObject {
+            name: uploaded.name,
+            size: uploaded.size as u64,
+        })
+    }
+
+    async fn list(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<StoredObject>, ObjectStoreError> {
+        let mut objects = Vec::new();
+        let mut page_token = None;
+
+        loop {
+            let response = self
+                .client
+                .list_objects(&ListObjectsRequest {
+                    bucket: bucket.to_string(),
+                    prefix: Some(prefix.to_string()),
+                    page_token: page_token.take(),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| Self::map_error(e, bucket, prefix))?;
+
+            objects.extend(
+                response
+                    .items
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|o| StoredObject {
+                        name: o.name,
+                        size: o.size as u64,
+                    }),
+            );
+
+            match response.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(objects)
+    }
+
+    async fn delete(&self, bucket: &str, object: &str) -> Result<(), ObjectStoreError> {
+        self.client
+            .delete_object(&DeleteObjectRequest {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))
+    }
+}
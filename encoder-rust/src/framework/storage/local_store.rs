@@ -0,0 +1,318 @@
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use futures::StreamExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::framework::storage::{ObjectReader, ObjectStore, ObjectStoreError, StoredObject};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// ObjectStore que guarda cada bucket como um subdiretório de `root`.
+/// Útil para rodar o pipeline localmente e nos testes, sem credenciais do GCP
+#[derive(Debug, Clone)]
+pub struct LocalObjectStore {
+    root: PathBuf,
+}
+
+impl LocalObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalObjectStore { root: root.into() }
+    }
+
+    /// Caminho do objeto dentro de `root`. Nomes com `..` ou componentes
+    /// absolutos são rejeitados, já que o nome pode vir de uma mensagem da fila
+    fn object_path(&self, bucket: &str, object: &str) -> Result<PathBuf, ObjectStoreError> {
+        let object_name = object.trim_start_matches('/');
+
+        if !is_safe_name(bucket) || !is_safe_name(object_name) {
+            return Err(ObjectStoreError::InvalidName {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+            });
+        }
+
+        Ok(self.root.join(bucket).join(object_name))
+    }
+
+    /// Caminho do bucket dentro de `root`, com a mesma validação dos objetos.
+    /// O prefixo vazio lista o bucket inteiro
+    fn bucket_path(&self, bucket: &str, prefix: &str) -> Result<PathBuf, ObjectStoreError> {
+        if !is_safe_name(bucket) || !(prefix.is_empty() || is_safe_name(prefix)) {
+            return Err(ObjectStoreError::InvalidName {
+                bucket: bucket.to_string(),
+                object: prefix.to_string(),
+            });
+        }
+
+        Ok(self.root.join(bucket))
+    }
+
+    fn map_error(e: std::io::Error, bucket: &str, object: &str) -> ObjectStoreError {
+        match e.kind() {
+            ErrorKind::NotFound => ObjectStoreError::NotFound {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+            },
+            _ => ObjectStoreError::Io(e),
+        }
+    }
+}
+
+impl ObjectStore for LocalObjectStore {
+    async fn get(&self, bucket: &str, object: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        tokio::fs::read(self.object_path(bucket, object)?)
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))
+    }
+
+    async fn get_stream(
+        &self,
+        bucket: &str,
+        object: &str,
+    ) -> Result<ObjectReader, ObjectStoreError> {
+        open_file(&self.object_path(bucket, object)?)
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))
+    }
+
+    async fn put(
+        &self,
+        bucket: &str,
+        object: &str,
+        data: Vec<u8>,
+    ) -> Result<StoredObject, ObjectStoreError> {
+        let path = self.object_path(bucket, object)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let size = data.len() as u64;
+        tokio::fs::write(&path, data).await?;
+
+        Ok(StoredObject {
+            name: object.to_string(),
+            size,
+        })
+    }
+
+    async fn put_stream(
+        &self,
+        bucket: &str,
+        object: &str,
+        mut reader: ObjectReader,
+    ) -> Result<StoredObject, ObjectStoreError> {
+        let path = self.object_path(bucket, object)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut file = tokio::fs::File::create(&path).await?;
+        let mut size = 0;
+
+        while let Some(chunk) = reader.stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            size += chunk.len() as u64;
+        }
+
+        file.flush().await?;
+
+        Ok(StoredObject {
+            name: object.to_string(),
+            size,
+        })
+    }
+
+    async fn list(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<StoredObject>, ObjectStoreError> {
+        let bucket_path = self.bucket_path(bucket, prefix)?;
+
+        if !tokio::fs::try_exists(&bucket_path).await? {
+            return Ok(Vec::new());
+        }
+
+        let mut objects = Vec::new();
+
+        for path in collect_files(&bucket_path).await? {
+            let name = path
+                .strip_prefix(&bucket_path)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string();
+
+            if name.starts_with(prefix) {
+                let size = tokio::fs::metadata(&path).await?.len();
+                objects.push(StoredObject { name, size });
+            }
+        }
+
+        Ok(objects)
+    }
+
+    async fn delete(&self, bucket: &str, object: &str) -> Result<(), ObjectStoreError> {
+        tokio::fs::remove_file(self.object_path(bucket, object)?)
+            .await
+            .map_err(|e| Self::map_error(e, bucket, object))
+    }
+}
+
+/// Nome não vazio formado só por componentes normais (sem `..`, `.` ou raiz)
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty()
+        && Path::new(name)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Abre um arquivo local para leitura em streaming, em chunks de `CHUNK_SIZE`
+pub async fn open_file(path: &Path) -> std::io::Result<ObjectReader> {
+    let file = tokio::fs::File::open(path).await?;
+    let size = file.metadata().await?.len();
+
+    let stream = futures::stream::try_unfold(file, |mut file| async move {
+        let mut chunk = vec![0; CHUNK_SIZE];
+        let read = file.read(&mut chunk).await?;
+
+        if read == 0 {
+            return Ok(None);
+        }
+
+        chunk.truncate(read);
+        Ok(Some((chunk, file)))
+    });
+
+    Ok(ObjectReader {
+        size,
+        stream: stream.boxed(),
+    })
+}
+
+/// Lista recursivamente todos os arquivos de um diretório, em ordem
+pub async fn collect_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&current).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+
+            if entry.file_type().await?.is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_object_store_crud() {
+        let root = std::env::temp_dir().join(format!("store-{}", uuid::Uuid::new_v4()));
+        let store = LocalObjectStore::new(&root);
+
+        let stored = store
+            .put("bucket", "videos/a/stream.mpd", b"<MPD/>".to_vec())
+            .await
+            .expect("Failed to put object");
+        assert_eq!(stored.size, 6);
+
+        store
+            .put("bucket", "videos/b/seg-1.m4s", b"segment".to_vec())
+            .await
+            .expect("Failed to put object");
+
+        let data = store
+            .get("bucket", "videos/a/stream.mpd")
+            .await
+            .expect("Failed to get object");
+        assert_eq!(data, b"<MPD/>");
+
+        let reader = store
+            .get_stream("bucket", "videos/b/seg-1.m4s")
+            .await
+            .expect("Failed to stream object");
+        assert_eq!(reader.size, 7);
+        let chunks: Vec<_> = reader.stream.collect().await;
+        let streamed: Vec<u8> = chunks.into_iter().flat_map(|c| c.unwrap()).collect();
+        assert_eq!(streamed, b"segment");
+
+        let reader = store
+            .get_stream("bucket", "videos/b/seg-1.m4s")
+            .await
+            .expect("Failed to stream object");
+        let copied = store
+            .put_stream("bucket", "videos/c/seg-1.m4s", reader)
+            .await
+            .expect("Failed to put object stream");
+        assert_eq!(copied.size, 7);
+        assert_eq!(
+            store.get("bucket", "videos/c/seg-1.m4s").await.unwrap(),
+            b"segment"
+        );
+
+        let listed = store
+            .list("bucket", "videos/a")
+            .await
+            .expect("Failed to list objects");
+        assert_eq!(
+            listed,
+            vec![StoredObject {
+                name: "videos/a/stream.mpd".to_string(),
+                size: 6,
+            }]
+        );
+
+        store
+            .delete("bucket", "videos/a/stream.mpd")
+            .await
+            .expect("Failed to delete object");
+
+        let result = store.get("bucket", "videos/a/stream.mpd").await;
+        assert!(matches!(result, Err(ObjectStoreError::NotFound { .. })));
+
+        // Nomes vindos da fila não podem sair do diretório do store
+        for (bucket, object) in [
+            ("bucket", "../outside.mp4"),
+            ("bucket", "videos/../../outside.mp4"),
+            ("..", "outside.mp4"),
+            ("/etc", "passwd"),
+        ] {
+            let result = store.put(bucket, object, b"data".to_vec()).await;
+            assert!(
+                matches!(result, Err(ObjectStoreError::InvalidName { .. })),
+                "{}/{} should be rejected",
+                bucket,
+                object
+            );
+        }
+        assert!(!root.join("outside.mp4").exists());
+
+        for (bucket, prefix) in [("../..", ""), ("/", ""), ("bucket", "../")] {
+            let result = store.list(bucket, prefix).await;
+            assert!(
+                matches!(result, Err(ObjectStoreError::InvalidName { .. })),
+                "listing {}/{} should be rejected",
+                bucket,
+                prefix
+            );
+        }
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+use std::pin::Pin;
+
+use futures::Stream;
+
+/// Metadados de um objeto armazenado
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredObject {
+    pub name: String,
+    pub size: u64,
+}
+
+/// Stream de chunks de um objeto, consumido sem carregar o objeto inteiro em memória
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Vec<u8>, ObjectStoreError>> + Send>>;
+
+/// Leitura em streaming de um objeto, com o tamanho total informado pelo backend
+pub struct ObjectReader {
+    pub size: u64,
+    pub stream: ByteStream,
+}
+
+#[derive(Debug)]
+pub enum ObjectStoreError {
+    NotFound {
+        bucket: String,
+        object: String,
+    },
+    /// Nome de bucket ou objeto que sairia do diretório do store (`..`, caminho absoluto)
+    InvalidName {
+        bucket: String,
+        object: String,
+    },
+    Io(std::io::Error),
+    Backend(String),
+}
+
+impl fmt::Display for ObjectStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectStoreError::NotFound { bucket, object } => {
+                write!(f, "object {} not found in bucket {}", object, bucket)
+            }
+            ObjectStoreError::InvalidName { bucket, object } => {
+                write!(f, "invalid object name {} in bucket {}", object, bucket)
+            }
+            ObjectStoreError::Io(e) => write!(f, "ObjectStore io error: {}", e),
+            ObjectStoreError::Backend(e) => write!(f, "ObjectStore error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ObjectStoreError {}
+
+impl From<std::io::Error> for ObjectStoreError {
+    fn from(e: std::io::Error) -> Self {
+        ObjectStoreError::Io(e)
+    }
+}
+
+/// Abstração do armazenamento de objetos (GCS, diretório local, ...)
+#[allow(async_fn_in_trait)]
+pub trait ObjectStore: Send + Sync {
+    async fn get(&self, bucket: &str, object: &str) -> Result<Vec<u8>, ObjectStoreError>;
+    async fn get_stream(
+        &self,
+        bucket: &str,
+        object: &str,
+    ) -> Result<ObjectReader, ObjectStoreError>;
+    async fn put(
+        &self,
+        bucket: &str,
+        object: &str,
+        data: Vec<u8>,
+    ) -> Result<StoredObject, ObjectStoreError>;
+    /// Grava o objeto a partir de um stream, sem carregar o conteúdo inteiro em memória
+    async fn put_stream(
+        &self,
+        bucket: &str,
+        object: &str,
+        reader: ObjectReader,
+    ) -> Result<StoredObject, ObjectStoreError>;
+    async fn list(&self, bucket: &str, prefix: &str)
+    -> Result<Vec<StoredObject>, ObjectStoreError>;
+    async fn delete(&self, bucket: &str, object: &str) -> Result<(), ObjectStoreError>;
+}
+
+/// MIME type do objeto a partir da extensão, para que o manifesto e os segmentos
+/// DASH sejam servidos com o tipo correto pelo bucket
+pub fn content_type(object: &str) -> &'static str {
+    match object
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+    {
+        Some(ext) if ext == "mpd" => "application/dash+xml",
+        Some(ext) if ext == "m4s" => "video/iso.segment",
+        Some(ext) if ext == "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_type_from_extension() {
+        assert_eq!(content_type("video-id/stream.mpd"), "application/dash+xml");
+        assert_eq!(
+            content_type("video-id/video/avc1/seg-1.m4s"),
+            "video/iso.segment"
+        );
+        assert_eq!(content_type("video-id/video/avc1/init.MP4"), "video/mp4");
+        assert_eq!(
+            content_type("video-id/notes.txt"),
+            "application/octet-stream"
+        );
+        assert_eq!(
+            content_type("video-id/no-extension"),
+            "application/octet-stream"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod application;
+pub mod config;
+pub mod domain;
+pub mod framework;
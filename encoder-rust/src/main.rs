@@ -1,23 +1,187 @@
-#![allow(unused, dead_code)]
+use std::sync::Arc;
 
+use anyhow::Context;
+use futures::StreamExt;
+use sqlx::Postgres;
+use tokio::sync::watch;
 use tracing_subscriber::EnvFilter;
 
-mod application;
-mod domain;
-mod framework;
+use encoder_rust::{
+    application::{
+        JobConsumer, JobNotifier, JobRecovery, JobRepository, JobWorker, RetryScheduler,
+        VideoRepository, unique_worker_id,
+    },
+    config::Config,
+    framework::{Database, GcsObjectStore, queue::RabbitMq},
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     init_logs();
 
-    println!("Hello, world!");
+    // Todos os problemas de configuração são reportados de uma vez
+    let config = Config::load()?;
+
+    let db = Database::<Postgres>::from_config(&config.database)
+        .await
+        .context("failed to connect to the database")?;
+    let job_repository = JobRepository::new(db.clone());
+    let video_repository = VideoRepository::new(db.clone());
+
+    // Jobs deixados para trás por um processo anterior voltam para a fila
+    // antes que os workers comecem a reivindicar jobs; depois a recuperação
+    // segue periódica, para os jobs de outras réplicas que morrerem
+    let recovery = JobRecovery::from_config(job_repository.clone(), &config);
+    recovery
+        .recover(chrono::Utc::now())
+        .await
+        .context("failed to recover stale jobs")?;
+
+    let store = GcsObjectStore::from_env()
+        .await
+        .context("failed to create the storage client")?;
+
+    // O handle fica com o main para fechar a conexão no desligamento
+    let rabbitmq = Arc::new(
+        RabbitMq::connect(config.rabbitmq.clone())
+            .await
+            .context("failed to connect to RabbitMQ")?,
+    );
+    let prefetch = u16::try_from(config.jobs.workers).unwrap_or(u16::MAX);
+    let deliveries = rabbitmq.consume(prefetch).await?;
+
+    let notifier = JobNotifier::new(
+        rabbitmq.clone(),
+        config.rabbitmq.notification_exchange.clone(),
+        config.rabbitmq.notification_routing_key.clone(),
+    );
+    let consumer = JobConsumer::from_config(
+        JobWorker::from_config(
+            video_repository.clone(),
+            job_repository.clone(),
+            store.clone(),
+            &config,
+        ),
+        notifier,
+        &config,
+    );
+
+    let scheduler = RetryScheduler::from_config(job_repository.clone(), &config.jobs);
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    let consume = async {
+        let deliveries = deliveries.take_until(shutdown_requested(shutdown_rx.clone()));
+        let result = consumer.run(deliveries, config.jobs.workers).await;
+
+        // Sem a fila não há como confirmar mensagens: os demais componentes
+        // também param
+        shutdown_tx.send_replace(true);
+        result
+    };
+
+    // Workers que reivindicam os jobs devolvidos para pending pelo
+    // RetryScheduler e pela recuperação
+    let poll = futures::future::join_all((0..config.jobs.workers).map(|_| {
+        let worker_id = unique_worker_id(&config.rabbitmq.consumer_name);
+        let shutdown = shutdown_rx.clone();
+        let consumer = &consumer;
+
+        async move {
+            consumer
+                .poll(&worker_id, config.jobs.poll_interval, shutdown)
+                .await
+        }
+    }));
+
+    let schedule = async {
+        tokio::select! {
+            _ = scheduler.run() => {}
+            _ = shutdown_requested(shutdown_rx.clone()) => {}
+        }
+    };
+
+    let recover = async {
+        tokio::select! {
+            _ = recovery.run() => {}
+            _ = shutdown_requested(shutdown_rx.clone()) => {}
+        }
+    };
+
+    tracing::info!(
+        "Encoder started with {} workers, consuming from {}",
+        config.jobs.workers,
+        config.rabbitmq.consumer_queue_name
+    );
+
+    let services = async {
+        let (consumed, _, _, _) = tokio::join!(consume, poll, schedule, recover);
+        consumed
+    };
+    tokio::pin!(services);
+
+    let result = tokio::select! {
+        result = &mut services => result,
+        signal = shutdown_signal() => {
+            signal?;
+            tracing::info!(
+                "Shutdown requested, waiting up to {:?} for in-flight jobs",
+                config.jobs.shutdown_timeout
+            );
+            shutdown_tx.send_replace(true);
+
+            // Após o prazo os jobs em andamento são abandonados; os checkpoints já
+            // gravados e a recuperação da próxima inicialização retomam o trabalho
+            match tokio::time::timeout(config.jobs.shutdown_timeout, &mut services).await {
+                Ok(result) => result,
+                Err(_) => {
+                    tracing::warn!("Shutdown deadline reached with jobs still in progress");
+                    Ok(())
+                }
+            }
+        }
+    };
+
+    // Mensagens recebidas e não confirmadas voltam para a fila ao fechar a conexão
+    if let Err(e) = rabbitmq.close().await {
+        tracing::warn!("Failed to close the RabbitMQ connection: {}", e);
+    }
+    db.conn.close().await;
+
+    result.context("job consumer stopped")?;
+    tracing::info!("Encoder stopped");
 
     Ok(())
 }
 
+/// Completa quando o desligamento é sinalizado no canal
+async fn shutdown_requested(mut shutdown: watch::Receiver<bool>) {
+    // Um canal fechado também encerra a espera
+    let _ = shutdown.wait_for(|requested| *requested).await;
+}
+
+/// Completa ao receber SIGTERM (docker stop) ou SIGINT (Ctrl+C)
+async fn shutdown_signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        let mut terminate =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = terminate.recv() => Ok(()),
+        }
+    }
+
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await
+}
+
 pub fn init_logs() {
     tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
         .with_test_writer()
         .try_init()
         .ok();